@@ -159,6 +159,128 @@ defmodule EDGAR do
     end
   end
 
+  @doc """
+  Fetches and parses the `-index.htm` page of a filing into its header and typed document list
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `accession_number` - The accession number of the filing
+  """
+  @spec filing_index(cik :: String.t(), accession_number :: String.t()) ::
+          success_type(map()) | error_type()
+  def filing_index(cik, accession_number) do
+    formatted_acc_no = String.replace(accession_number, "-", "")
+
+    filing_index_from_url(
+      "#{@edgar_archives_url}/data/#{cik}/#{formatted_acc_no}/#{accession_number}-index.htm"
+    )
+  end
+
+  @doc """
+  Parses a filing `-index.htm` page from a file
+
+  ## Required
+
+  * `file_path` - The path to the file
+  """
+  @spec filing_index_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def filing_index_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: filing_index_from_string(body)
+  end
+
+  @doc """
+  Parses a filing `-index.htm` page from a given url
+
+  ## Required
+
+  * `url` - The url of the index page to parse
+  """
+  @spec filing_index_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def filing_index_from_url(url) do
+    with {:ok, body} <- get(url), do: filing_index_from_string(body)
+  end
+
+  @doc """
+  Parses a filing `-index.htm` page from a string
+
+  ## Required
+
+  * `html_str` - The html string to parse
+  """
+  @spec filing_index_from_string(html_str :: String.t()) :: success_type(map()) | error_type()
+  def filing_index_from_string(html_str), do: EDGAR.Native.parse_filing_index(html_str)
+
+  @doc """
+  Fetches and parses the `-index-headers.html` page of a filing into its header and typed document list
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `accession_number` - The accession number of the filing
+  """
+  @spec filing_index_headers(cik :: String.t(), accession_number :: String.t()) ::
+          success_type(map()) | error_type()
+  def filing_index_headers(cik, accession_number) do
+    formatted_acc_no = String.replace(accession_number, "-", "")
+
+    filing_index_headers_from_url(
+      "#{@edgar_archives_url}/data/#{cik}/#{formatted_acc_no}/#{accession_number}-index-headers.html"
+    )
+  end
+
+  @doc """
+  Parses a filing `-index-headers.html` page from a file
+
+  ## Required
+
+  * `file_path` - The path to the file
+  """
+  @spec filing_index_headers_from_file(file_path :: String.t()) ::
+          success_type(map()) | error_type()
+  def filing_index_headers_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: filing_index_headers_from_string(body)
+  end
+
+  @doc """
+  Parses a filing `-index-headers.html` page from a given url
+
+  ## Required
+
+  * `url` - The url of the headers page to parse
+  """
+  @spec filing_index_headers_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def filing_index_headers_from_url(url) do
+    with {:ok, body} <- get(url), do: filing_index_headers_from_string(body)
+  end
+
+  @doc """
+  Parses a filing `-index-headers.html` page from a string
+
+  ## Required
+
+  * `html_str` - The html string to parse
+  """
+  @spec filing_index_headers_from_string(html_str :: String.t()) ::
+          success_type(map()) | error_type()
+  def filing_index_headers_from_string(html_str),
+    do: EDGAR.Native.parse_filing_index_headers(html_str)
+
+  @doc """
+  Finds the first xml document in a parsed filing index whose declared type is one of `types`
+
+  ## Required
+
+  * `index` - The parsed filing index
+  * `types` - The document types to match, e.g. `["4", "4/A"]`
+  """
+  @spec find_index_document(index :: map(), types :: [String.t()]) :: map() | nil
+  def find_index_document(index, types) do
+    Enum.find(index.documents, fn document ->
+      document.document_type in types and String.ends_with?(document.name, ".xml")
+    end)
+  end
+
   @doc """
   Fetches the daily index
 
@@ -588,6 +710,115 @@ defmodule EDGAR do
     |> get()
   end
 
+  @doc """
+  Fetches company facts for a given CIK as a flat fact table
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+
+  ## Optional
+
+  * `concepts` - A list of concepts to include, e.g. `["Revenues", "dei:EntityRegistrantName"]`
+  * `dedupe_frames` - Keep only the fact that represents each calendar frame
+  """
+  @spec company_facts_table(cik :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def company_facts_table(cik, opts \\ %{}) do
+    padded_cik = String.pad_leading(cik, 10, "0")
+    url = "#{@edgar_data_url}/api/xbrl/companyfacts/CIK#{padded_cik}.json"
+
+    with {:ok, body} <- get(url, decode_body: false), do: fact_table_from_string(body, opts)
+  end
+
+  @doc """
+  Fetches a company concept for a given CIK as a flat fact table
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `taxonomy` - The taxonomy of the concept
+  * `tag` - The tag of the concept
+
+  ## Optional
+
+  * `dedupe_frames` - Keep only the fact that represents each calendar frame
+  """
+  @spec company_concept_table(
+          cik :: String.t(),
+          taxonomy :: String.t(),
+          tag :: String.t(),
+          opts :: map()
+        ) ::
+          success_type(map()) | error_type()
+  def company_concept_table(cik, taxonomy, tag, opts \\ %{}) do
+    padded_cik = String.pad_leading(cik, 10, "0")
+    url = "#{@edgar_data_url}/api/xbrl/companyconcept/CIK#{padded_cik}/#{taxonomy}/#{tag}.json"
+
+    with {:ok, body} <- get(url, decode_body: false), do: fact_table_from_string(body, opts)
+  end
+
+  @doc """
+  Fetches frames for a given taxonomy, concept, unit, and period as a flat fact table
+
+  ## Required
+
+  * `taxonomy` - The taxonomy of the concept
+  * `tag` - The tag of the concept
+  * `unit` - The unit of the concept
+  * `period` - The period of the concept
+  """
+  @spec frames_table(
+          taxonomy :: String.t(),
+          tag :: String.t(),
+          unit :: String.t(),
+          period :: String.t()
+        ) ::
+          success_type(map()) | error_type()
+  def frames_table(taxonomy, tag, unit, period) do
+    url = "#{@edgar_data_url}/api/xbrl/frames/#{taxonomy}/#{tag}/#{unit}/#{period}.json"
+
+    with {:ok, body} <- get(url, decode_body: false), do: fact_table_from_string(body)
+  end
+
+  @doc """
+  Parses companyfacts, companyconcept, or frames json from a file into a flat fact table
+
+  ## Required
+
+  * `file_path` - The path to the json file
+
+  ## Optional
+
+  * `concepts` - A list of concepts to include
+  * `dedupe_frames` - Keep only the fact that represents each calendar frame
+  """
+  @spec fact_table_from_file(file_path :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def fact_table_from_file(file_path, opts \\ %{}) do
+    with {:ok, body} <- File.read(file_path), do: fact_table_from_string(body, opts)
+  end
+
+  @doc """
+  Parses companyfacts, companyconcept, or frames json from a string into a flat fact table
+
+  ## Required
+
+  * `json_str` - The json string to parse
+
+  ## Optional
+
+  * `concepts` - A list of concepts to include
+  * `dedupe_frames` - Keep only the fact that represents each calendar frame
+  """
+  @spec fact_table_from_string(json_str :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def fact_table_from_string(json_str, opts \\ %{}) do
+    options = %{concepts: opts[:concepts], dedupe_frames: opts[:dedupe_frames] || false}
+
+    EDGAR.Native.normalize_company_facts(json_str, options)
+  end
+
   @doc """
   Fetches a list of filings from the submissions file
 
@@ -639,6 +870,123 @@ defmodule EDGAR do
   defp form_type(filings, form_type),
     do: Enum.filter(filings, fn filing -> filing["form"] == form_type end)
 
+  @doc """
+  Fetches typed filing rows from the submissions file, including the older paging files
+
+  Rows are decoded in rust into maps with snake_case keys, see `submissions_from_string/2`.
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+
+  ## Optional
+
+  * `form_type` - The form type of the filing
+  * `form_types` - A list of form types to include
+  * `date_from` - The earliest filing date to include, as a `Date` or `"YYYY-MM-DD"`
+  * `date_to` - The latest filing date to include, as a `Date` or `"YYYY-MM-DD"`
+  * `offset` - The offset of the filings
+  * `limit` - The limit of the filings
+  """
+  @spec filing_rows(cik :: String.t(), opts :: map()) :: success_type(list()) | error_type()
+  def filing_rows(cik, opts \\ %{}) do
+    padded_cik = String.pad_leading(cik, 10, "0")
+    filter = submissions_filter(opts)
+
+    with {:ok, body} <-
+           get("#{@edgar_data_url}/submissions/CIK#{padded_cik}.json", decode_body: false),
+         {:ok, submissions} <- EDGAR.Native.parse_submissions(body, filter),
+         {:ok, file_filings} <- file_filings(submissions.files, filter) do
+      filings =
+        (submissions.filings ++ file_filings)
+        |> offset(opts[:offset])
+        |> limit(opts[:limit])
+
+      {:ok, filings}
+    end
+  end
+
+  @doc """
+  Parses a submissions file, or one of its paging files, from a file
+
+  ## Required
+
+  * `file_path` - The path to the submissions json file
+
+  ## Optional
+
+  * `form_types` - A list of form types to include
+  * `date_from` - The earliest filing date to include
+  * `date_to` - The latest filing date to include
+  """
+  @spec submissions_from_file(file_path :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def submissions_from_file(file_path, opts \\ %{}) do
+    with {:ok, body} <- File.read(file_path), do: submissions_from_string(body, opts)
+  end
+
+  @doc """
+  Parses a submissions file, or one of its paging files, from a string
+
+  ## Required
+
+  * `json_str` - The submissions json string to parse
+
+  ## Optional
+
+  * `form_types` - A list of form types to include
+  * `date_from` - The earliest filing date to include
+  * `date_to` - The latest filing date to include
+  """
+  @spec submissions_from_string(json_str :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def submissions_from_string(json_str, opts \\ %{}),
+    do: EDGAR.Native.parse_submissions(json_str, submissions_filter(opts))
+
+  defp submissions_filter(opts) do
+    form_types =
+      case {opts[:form_types], opts[:form_type]} do
+        {nil, nil} -> nil
+        {nil, form_type} -> [form_type]
+        {form_types, _} -> form_types
+      end
+
+    %{
+      form_types: form_types,
+      date_from: format_filter_date(opts[:date_from]),
+      date_to: format_filter_date(opts[:date_to])
+    }
+  end
+
+  defp format_filter_date(nil), do: nil
+  defp format_filter_date(%Date{} = date), do: Date.to_iso8601(date)
+  defp format_filter_date(date), do: date
+
+  defp file_filings(files, filter) do
+    files
+    |> Enum.filter(&file_in_range?(&1, filter))
+    |> Enum.reduce_while({:ok, []}, fn file, {:ok, acc} ->
+      with {:ok, body} <-
+             get("#{@edgar_data_url}/submissions/#{file.name}", decode_body: false),
+           {:ok, page} <- EDGAR.Native.parse_submissions(body, filter) do
+        {:cont, {:ok, [page.filings | acc]}}
+      else
+        error -> {:halt, error}
+      end
+    end)
+    |> case do
+      {:ok, pages} -> {:ok, pages |> Enum.reverse() |> Enum.concat()}
+      error -> error
+    end
+  end
+
+  # Paging files declare the filing date range they cover, so ones outside the
+  # filter can be skipped without fetching them.
+  defp file_in_range?(file, filter) do
+    (is_nil(filter.date_from) or is_nil(file.filing_to) or file.filing_to >= filter.date_from) and
+      (is_nil(filter.date_to) or is_nil(file.filing_from) or file.filing_from <= filter.date_to)
+  end
+
   defp offset(filings, offset) when is_nil(offset), do: filings
   defp offset(filings, offset), do: Enum.drop(filings, offset)
 
@@ -672,10 +1020,15 @@ defmodule EDGAR do
 
   * `cik` - The CIK of the entity
   * `accession_number` - The accession number of the filing
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form3_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+  @spec form3_from_filing(cik :: String.t(), accession_number :: String.t(), opts :: map()) ::
           success_type(map()) | error_type()
-  def form3_from_filing(cik, accession_number), do: ownership_from_filing(cik, accession_number)
+  def form3_from_filing(cik, accession_number, opts \\ %{}),
+    do: ownership_from_filing(cik, accession_number, opts)
 
   @doc """
   Parses form 3 and 3/A ownership filing types from a given file path
@@ -683,9 +1036,14 @@ defmodule EDGAR do
   ## Required
 
   * `file_path` - The path of the form 3 filing to parse
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form3_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
-  def form3_from_file(file_path), do: ownership_form_from_file(file_path)
+  @spec form3_from_file(file_path :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def form3_from_file(file_path, opts \\ %{}), do: ownership_form_from_file(file_path, opts)
 
   @doc """
   Parses form 3 and 3/A ownership filing types from a given url
@@ -693,9 +1051,13 @@ defmodule EDGAR do
   ## Required
 
   * `url` - The url of the form 3 filing
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form3_from_url(url :: String.t()) :: success_type(map()) | error_type()
-  def form3_from_url(url), do: ownership_form_from_url(url)
+  @spec form3_from_url(url :: String.t(), opts :: map()) :: success_type(map()) | error_type()
+  def form3_from_url(url, opts \\ %{}), do: ownership_form_from_url(url, opts)
 
   @doc """
   Parses form 3 and 3/A ownership filing types from a given string
@@ -703,9 +1065,14 @@ defmodule EDGAR do
   ## Required
 
   * `xml_str` - The xml string of the form 3 filing to parse
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form3_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
-  def form3_from_string(xml_str), do: ownership_form_from_string(xml_str)
+  @spec form3_from_string(xml_str :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def form3_from_string(xml_str, opts \\ %{}), do: ownership_form_from_string(xml_str, opts)
 
   @doc """
   Parses form 4 and 4/A filing types from a given CIK and accession number
@@ -714,10 +1081,15 @@ defmodule EDGAR do
 
   * `cik` - The CIK of the entity
   * `accession_number` - The accession number of the filing
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form4_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+  @spec form4_from_filing(cik :: String.t(), accession_number :: String.t(), opts :: map()) ::
           success_type(map()) | error_type()
-  def form4_from_filing(cik, accession_number), do: ownership_from_filing(cik, accession_number)
+  def form4_from_filing(cik, accession_number, opts \\ %{}),
+    do: ownership_from_filing(cik, accession_number, opts)
 
   @doc """
   Parses form 4 and 4/A ownership filing types from a given file path
@@ -725,9 +1097,14 @@ defmodule EDGAR do
   ## Required
 
   * `file_path` - The path of the form 4 filing to parse
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form4_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
-  def form4_from_file(file_path), do: ownership_form_from_file(file_path)
+  @spec form4_from_file(file_path :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def form4_from_file(file_path, opts \\ %{}), do: ownership_form_from_file(file_path, opts)
 
   @doc """
   Parses form 4 and 4/A ownership filing types from a given url
@@ -735,9 +1112,13 @@ defmodule EDGAR do
   ## Required
 
   * `url` - The url of the form 3 filing
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form4_from_url(url :: String.t()) :: success_type(map()) | error_type()
-  def form4_from_url(url), do: ownership_form_from_url(url)
+  @spec form4_from_url(url :: String.t(), opts :: map()) :: success_type(map()) | error_type()
+  def form4_from_url(url, opts \\ %{}), do: ownership_form_from_url(url, opts)
 
   @doc """
   Parses form 4 and 4/A ownership filing types from a given string
@@ -745,9 +1126,14 @@ defmodule EDGAR do
   ## Required
 
   * `xml_str` - The xml string of the form 4 filing to parse
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form4_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
-  def form4_from_string(xml_str), do: ownership_form_from_string(xml_str)
+  @spec form4_from_string(xml_str :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def form4_from_string(xml_str, opts \\ %{}), do: ownership_form_from_string(xml_str, opts)
 
   @doc """
   Parses form 5 and 5/A filing types from a given CIK and accession number
@@ -756,10 +1142,15 @@ defmodule EDGAR do
 
   * `cik` - The CIK of the entity
   * `accession_number` - The accession number of the filing
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form5_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+  @spec form5_from_filing(cik :: String.t(), accession_number :: String.t(), opts :: map()) ::
           success_type(map()) | error_type()
-  def form5_from_filing(cik, accession_number), do: ownership_from_filing(cik, accession_number)
+  def form5_from_filing(cik, accession_number, opts \\ %{}),
+    do: ownership_from_filing(cik, accession_number, opts)
 
   @doc """
   Parses form 5 and 5/A ownership filing types from a given file path
@@ -767,9 +1158,14 @@ defmodule EDGAR do
   ## Required
 
   * `file_path` - The path of the form 5 filing to parse
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form5_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
-  def form5_from_file(file_path), do: ownership_form_from_file(file_path)
+  @spec form5_from_file(file_path :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def form5_from_file(file_path, opts \\ %{}), do: ownership_form_from_file(file_path, opts)
 
   @doc """
   Parses form 5 and 5/A ownership filing types from a given url
@@ -777,9 +1173,13 @@ defmodule EDGAR do
   ## Required
 
   * `url` - The url of the form 3 filing
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form5_from_url(url :: String.t()) :: success_type(map()) | error_type()
-  def form5_from_url(url), do: ownership_form_from_url(url)
+  @spec form5_from_url(url :: String.t(), opts :: map()) :: success_type(map()) | error_type()
+  def form5_from_url(url, opts \\ %{}), do: ownership_form_from_url(url, opts)
 
   @doc """
   Parses form 5 and 5/A ownership filing types from a given string
@@ -787,9 +1187,14 @@ defmodule EDGAR do
   ## Required
 
   * `xml_str` - The xml string of the form 5 filing to parse
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec form5_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
-  def form5_from_string(xml_str), do: ownership_form_from_string(xml_str)
+  @spec form5_from_string(xml_str :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def form5_from_string(xml_str, opts \\ %{}), do: ownership_form_from_string(xml_str, opts)
 
   @doc """
   Parses form 3, 3/A, 4, 4/A, 5, and 5/A ownership filing types from a given CIK and accession number
@@ -798,23 +1203,25 @@ defmodule EDGAR do
 
   * `cik` - The CIK of the entity
   * `accession_number` - The accession number of the filing
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec ownership_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+  @spec ownership_from_filing(cik :: String.t(), accession_number :: String.t(), opts :: map()) ::
           success_type(map()) | error_type()
-  def ownership_from_filing(cik, accession_number) do
-    case filing_directory(cik, accession_number) do
-      {:ok, dir} ->
-        files = dir["directory"]["item"]
-
-        case Enum.find(files, fn file -> String.ends_with?(file["name"], ".xml") end) do
+  def ownership_from_filing(cik, accession_number, opts \\ %{}) do
+    case filing_index(cik, accession_number) do
+      {:ok, index} ->
+        case find_index_document(index, ["3", "3/A", "4", "4/A", "5", "5/A"]) do
           nil ->
-            {:error, "No xml file found"}
+            {:error, "No ownership document found"}
 
-          xml_file ->
+          document ->
             acc_no = String.replace(accession_number, "-", "")
-            xml_file_url = "#{@edgar_archives_url}/data/#{cik}/#{acc_no}/#{xml_file["name"]}"
+            xml_file_url = "#{@edgar_archives_url}/data/#{cik}/#{acc_no}/#{document.name}"
 
-            ownership_form_from_url(xml_file_url)
+            ownership_form_from_url(xml_file_url, opts)
         end
 
       error ->
@@ -828,10 +1235,15 @@ defmodule EDGAR do
   ## Required
 
   * `file_path` - The path to the file
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec ownership_form_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
-  def ownership_form_from_file(file_path) do
-    with {:ok, body} <- File.read(file_path), do: ownership_form_from_string(body)
+  @spec ownership_form_from_file(file_path :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def ownership_form_from_file(file_path, opts \\ %{}) do
+    with {:ok, body} <- File.read(file_path), do: ownership_form_from_string(body, opts)
   end
 
   @doc """
@@ -840,10 +1252,15 @@ defmodule EDGAR do
   ## Required
 
   * `url` - The url of the form 4 to parse
+
+  ## Optional
+
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
   """
-  @spec ownership_form_from_url(url :: String.t()) :: success_type(map()) | error_type()
-  def ownership_form_from_url(url) do
-    with {:ok, body} <- get(url), do: ownership_form_from_string(body)
+  @spec ownership_form_from_url(url :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def ownership_form_from_url(url, opts \\ %{}) do
+    with {:ok, body} <- get(url), do: ownership_form_from_string(body, opts)
   end
 
   @doc """
@@ -852,13 +1269,61 @@ defmodule EDGAR do
   ## Required
 
   * `form_str` - The document string to parse
-  """
-  @spec ownership_form_from_string(form_str :: String.t()) :: success_type(map()) | error_type()
-  def ownership_form_from_string(form_str), do: EDGAR.Native.parse_ownership_form(form_str)
 
-  @doc """
+  ## Optional
 
-  Parses a form 13F filing for a given CIK and accession number
+  * `resolve_footnotes` - Resolve the footnote texts cited by each value into its `footnotes`
+  """
+  @spec ownership_form_from_string(form_str :: String.t(), opts :: map()) ::
+          success_type(map()) | error_type()
+  def ownership_form_from_string(form_str, opts \\ %{}) do
+    options = %{resolve_footnotes: opts[:resolve_footnotes] || false}
+
+    EDGAR.Native.parse_ownership_form(form_str, options)
+  end
+
+  @doc """
+  Merges parsed ownership forms and their amendments into the effective transactions
+
+  Originals are applied first and amendments (e.g. 4/A) in the given order. An amended
+  transaction replaces the one with the same period of report, security title,
+  transaction date and code, preferring the one at the same position in the table. When
+  none has the same date, the one at the same position with the same security title and
+  code is replaced, so corrected dates are matched. Amended transactions are returned
+  with `status: "amended"` and the `changed_fields` that differ, ignoring resolved
+  footnote texts. Amendment transactions that match none are returned as `"added"`, and
+  transactions an amendment leaves out are kept as `"original"`.
+
+  ## Required
+
+  * `forms` - Parsed ownership forms for the same issuer and reporting owner
+  """
+  @spec merge_ownership_amendments(forms :: list(map())) :: success_type(map()) | error_type()
+  def merge_ownership_amendments(forms), do: EDGAR.Native.merge_ownership_amendments(forms)
+
+  @doc """
+  Normalizes the transactions of a parsed ownership form into flat typed records
+
+  Each record has the decoded `transaction_description` of its code, the signed
+  `share_change` (negative for dispositions), the dollar `value` (the reported total
+  value or shares times price), whether it `is_open_market` (codes P and S) and whether
+  it `is_10b5_1_plan`, either from the `aff10b5One` checkbox (for open market trades)
+  or a referenced footnote stating it was made under a Rule 10b5-1 plan.
+
+  Referenced footnotes also fill in the `price_range_low` and `price_range_high` behind
+  a weighted average price, the `plan_adoption_date` of a 10b5-1 plan, and for indirect
+  holdings the `indirect_ownership_description` and `trust_name` of a trust.
+
+  ## Required
+
+  * `form` - A parsed form 3, 4 or 5
+  """
+  @spec insider_transactions(form :: map()) :: list(map())
+  def insider_transactions(form), do: EDGAR.Native.normalize_insider_transactions(form)
+
+  @doc """
+
+  Parses a form 13F filing for a given CIK and accession number
 
   ## Required
 
@@ -868,25 +1333,19 @@ defmodule EDGAR do
   @spec form13f_from_filing(cik :: String.t(), accession_number :: String.t()) ::
           success_type(map()) | error_type()
   def form13f_from_filing(cik, accession_number) do
-    case filing_directory(cik, accession_number) do
-      {:ok, dir} ->
-        files = dir["directory"]["item"]
-
-        primary_doc_file = Enum.find(files, fn file -> file["name"] == "primary_doc.xml" end)
-
-        table_xml_file =
-          Enum.find(files, fn file ->
-            file["name"] != "primary_doc.xml" and String.ends_with?(file["name"], ".xml")
-          end)
+    case filing_index(cik, accession_number) do
+      {:ok, index} ->
+        primary_doc_file = find_index_document(index, ["13F-HR", "13F-HR/A"])
+        table_xml_file = find_index_document(index, ["INFORMATION TABLE"])
 
         if primary_doc_file && table_xml_file do
           formatted_acc_no = String.replace(accession_number, "-", "")
 
           primary_doc_url =
-            "#{@edgar_archives_url}/data/#{cik}/#{formatted_acc_no}/#{primary_doc_file["name"]}"
+            "#{@edgar_archives_url}/data/#{cik}/#{formatted_acc_no}/#{primary_doc_file.name}"
 
           table_xml_url =
-            "#{@edgar_archives_url}/data/#{cik}/#{formatted_acc_no}/#{table_xml_file["name"]}"
+            "#{@edgar_archives_url}/data/#{cik}/#{formatted_acc_no}/#{table_xml_file.name}"
 
           with {:ok, document} <- form13f_document_from_url(primary_doc_url),
                {:ok, table} <- form13f_table_from_url(table_xml_url) do
@@ -971,6 +1430,775 @@ defmodule EDGAR do
   @spec form13f_table_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
   def form13f_table_from_string(xml_str), do: EDGAR.Native.parse_form13f_table(xml_str)
 
+  @doc """
+  Cross-checks a parsed form 13F document summary page against its parsed table
+
+  Reports the declared and actual entry count and value total, other manager
+  sequence numbers used in the table but not declared on the summary page,
+  and rows that appear more than once, by their 1-based position in the table.
+
+  ## Required
+
+  * `document` - The parsed 13F primary document
+  * `table` - The parsed 13F information table
+  """
+  @spec form13f_consistency(document :: map(), table :: map()) ::
+          success_type(map()) | error_type()
+  def form13f_consistency(document, table),
+    do: EDGAR.Native.check_form13f_consistency(document, table)
+
+  @doc """
+  Parses a form 144 and 144/A filing for a given CIK and accession number
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `accession_number` - The accession number of the filing
+  """
+  @spec form144_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+          success_type(map()) | error_type()
+  def form144_from_filing(cik, accession_number) do
+    case filing_index(cik, accession_number) do
+      {:ok, index} ->
+        case find_index_document(index, ["144", "144/A"]) do
+          nil ->
+            {:error, "No form 144 document found"}
+
+          document ->
+            acc_no = String.replace(accession_number, "-", "")
+            xml_file_url = "#{@edgar_archives_url}/data/#{cik}/#{acc_no}/#{document.name}"
+
+            form144_from_url(xml_file_url)
+        end
+
+      error ->
+        error
+    end
+  end
+
+  @doc """
+  Parses a form 144 document from a given file path
+
+  ## Required
+
+  * `file_path` - The path to the form 144 document to parse
+  """
+  @spec form144_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def form144_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: form144_from_string(body)
+  end
+
+  @doc """
+  Parses a form 144 document from a given url
+
+  ## Required
+
+  * `url` - The url of the form 144 document to parse
+  """
+  @spec form144_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def form144_from_url(url) do
+    with {:ok, body} <- get(url), do: form144_from_string(body)
+  end
+
+  @doc """
+  Parses a form 144 document from a string
+
+  ## Required
+
+  * `xml_str` - The form 144 xml string to parse
+  """
+  @spec form144_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
+  def form144_from_string(xml_str), do: EDGAR.Native.parse_form144(xml_str)
+
+  @doc """
+  Parses an IAPD Form ADV firm compilation feed from a given file path
+
+  The feeds are published at https://adviserinfo.sec.gov/compilation and
+  each firm is keyed by its CRD number.
+
+  ## Required
+
+  * `file_path` - The path to the Form ADV feed xml to parse
+  """
+  @spec adv_feed_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def adv_feed_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: adv_feed_from_string(body)
+  end
+
+  @doc """
+  Parses an IAPD Form ADV firm compilation feed from a string
+
+  ## Required
+
+  * `xml_str` - The Form ADV feed xml string to parse
+  """
+  @spec adv_feed_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
+  def adv_feed_from_string(xml_str), do: EDGAR.Native.parse_adv_feed(xml_str)
+
+  @doc """
+  Parses a form C, C/A, C-U, C-AR, or C-TR filing for a given CIK and accession number
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `accession_number` - The accession number of the filing
+  """
+  @spec formc_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+          success_type(map()) | error_type()
+  def formc_from_filing(cik, accession_number) do
+    case filing_index(cik, accession_number) do
+      {:ok, index} ->
+        case find_index_document(index, ["C", "C/A", "C-U", "C-U/A", "C-AR", "C-AR/A", "C-TR"]) do
+          nil ->
+            {:error, "No form C document found"}
+
+          document ->
+            acc_no = String.replace(accession_number, "-", "")
+            xml_file_url = "#{@edgar_archives_url}/data/#{cik}/#{acc_no}/#{document.name}"
+
+            formc_from_url(xml_file_url)
+        end
+
+      error ->
+        error
+    end
+  end
+
+  @doc """
+  Parses a form C document from a given file path
+
+  ## Required
+
+  * `file_path` - The path to the form C document to parse
+  """
+  @spec formc_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def formc_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: formc_from_string(body)
+  end
+
+  @doc """
+  Parses a form C document from a given url
+
+  ## Required
+
+  * `url` - The url of the form C document to parse
+  """
+  @spec formc_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def formc_from_url(url) do
+    with {:ok, body} <- get(url), do: formc_from_string(body)
+  end
+
+  @doc """
+  Parses a form C document from a string
+
+  ## Required
+
+  * `xml_str` - The form C xml string to parse
+  """
+  @spec formc_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
+  def formc_from_string(xml_str), do: EDGAR.Native.parse_formc(xml_str)
+
+  @doc """
+  Parses a form D and D/A filing for a given CIK and accession number
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `accession_number` - The accession number of the filing
+  """
+  @spec formd_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+          success_type(map()) | error_type()
+  def formd_from_filing(cik, accession_number) do
+    case filing_index(cik, accession_number) do
+      {:ok, index} ->
+        case find_index_document(index, ["D", "D/A"]) do
+          nil ->
+            {:error, "No form D document found"}
+
+          document ->
+            acc_no = String.replace(accession_number, "-", "")
+            xml_file_url = "#{@edgar_archives_url}/data/#{cik}/#{acc_no}/#{document.name}"
+
+            formd_from_url(xml_file_url)
+        end
+
+      error ->
+        error
+    end
+  end
+
+  @doc """
+  Parses a form D document from a given file path
+
+  ## Required
+
+  * `file_path` - The path to the form D document to parse
+  """
+  @spec formd_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def formd_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: formd_from_string(body)
+  end
+
+  @doc """
+  Parses a form D document from a given url
+
+  ## Required
+
+  * `url` - The url of the form D document to parse
+  """
+  @spec formd_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def formd_from_url(url) do
+    with {:ok, body} <- get(url), do: formd_from_string(body)
+  end
+
+  @doc """
+  Parses a form D document from a string
+
+  ## Required
+
+  * `xml_str` - The form D xml string to parse
+  """
+  @spec formd_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
+  def formd_from_string(xml_str), do: EDGAR.Native.parse_formd(xml_str)
+
+  @doc """
+  Parses a form N-MFP2 or N-MFP3 filing for a given CIK and accession number
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `accession_number` - The accession number of the filing
+  """
+  @spec nmfp_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+          success_type(map()) | error_type()
+  def nmfp_from_filing(cik, accession_number) do
+    case filing_index(cik, accession_number) do
+      {:ok, index} ->
+        case find_index_document(index, ["N-MFP2", "N-MFP2/A", "N-MFP3", "N-MFP3/A"]) do
+          nil ->
+            {:error, "No form N-MFP document found"}
+
+          document ->
+            acc_no = String.replace(accession_number, "-", "")
+            xml_file_url = "#{@edgar_archives_url}/data/#{cik}/#{acc_no}/#{document.name}"
+
+            nmfp_from_url(xml_file_url)
+        end
+
+      error ->
+        error
+    end
+  end
+
+  @doc """
+  Parses a form N-MFP document from a given file path
+
+  ## Required
+
+  * `file_path` - The path to the form N-MFP document to parse
+  """
+  @spec nmfp_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def nmfp_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: nmfp_from_string(body)
+  end
+
+  @doc """
+  Parses a form N-MFP document from a given url
+
+  ## Required
+
+  * `url` - The url of the form N-MFP document to parse
+  """
+  @spec nmfp_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def nmfp_from_url(url) do
+    with {:ok, body} <- get(url), do: nmfp_from_string(body)
+  end
+
+  @doc """
+  Parses a form N-MFP document from a string
+
+  ## Required
+
+  * `xml_str` - The form N-MFP xml string to parse
+  """
+  @spec nmfp_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
+  def nmfp_from_string(xml_str), do: EDGAR.Native.parse_nmfp(xml_str)
+
+  @doc """
+  Parses a form N-PORT filing for a given CIK and accession number
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `accession_number` - The accession number of the filing
+  """
+  @spec nport_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+          success_type(map()) | error_type()
+  def nport_from_filing(cik, accession_number) do
+    case filing_index(cik, accession_number) do
+      {:ok, index} ->
+        case find_index_document(index, ["NPORT-P", "NPORT-P/A"]) do
+          nil ->
+            {:error, "No form N-PORT document found"}
+
+          document ->
+            acc_no = String.replace(accession_number, "-", "")
+            xml_file_url = "#{@edgar_archives_url}/data/#{cik}/#{acc_no}/#{document.name}"
+
+            nport_from_url(xml_file_url)
+        end
+
+      error ->
+        error
+    end
+  end
+
+  @doc """
+  Parses a form N-PORT document from a given file path
+
+  ## Required
+
+  * `file_path` - The path to the N-PORT document to parse
+  """
+  @spec nport_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def nport_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: nport_from_string(body)
+  end
+
+  @doc """
+  Parses a form N-PORT document from a given url
+
+  ## Required
+
+  * `url` - The url of the N-PORT document to parse
+  """
+  @spec nport_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def nport_from_url(url) do
+    with {:ok, body} <- get(url), do: nport_from_string(body)
+  end
+
+  @doc """
+  Parses a form N-PORT document from a string
+
+  ## Required
+
+  * `xml_str` - The N-PORT xml string to parse
+  """
+  @spec nport_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
+  def nport_from_string(xml_str), do: EDGAR.Native.parse_nport(xml_str)
+
+  @doc """
+  Parses a form N-PX filing for a given CIK and accession number
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `accession_number` - The accession number of the filing
+  """
+  @spec npx_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+          success_type(map()) | error_type()
+  def npx_from_filing(cik, accession_number) do
+    case filing_index(cik, accession_number) do
+      {:ok, index} ->
+        primary_doc_file = find_index_document(index, ["N-PX", "N-PX/A"])
+        table_xml_file = find_index_document(index, ["PROXY VOTING RECORD"])
+
+        if primary_doc_file && table_xml_file do
+          formatted_acc_no = String.replace(accession_number, "-", "")
+
+          primary_doc_url =
+            "#{@edgar_archives_url}/data/#{cik}/#{formatted_acc_no}/#{primary_doc_file.name}"
+
+          table_xml_url =
+            "#{@edgar_archives_url}/data/#{cik}/#{formatted_acc_no}/#{table_xml_file.name}"
+
+          with {:ok, document} <- npx_document_from_url(primary_doc_url),
+               {:ok, table} <- npx_table_from_url(table_xml_url) do
+            {:ok, %{document: document, table: table}}
+          else
+            error -> error
+          end
+        else
+          {:error, "No primary_doc or table file found"}
+        end
+
+      error ->
+        error
+    end
+  end
+
+  @doc """
+  Parses a form N-PX document from a given file path
+
+  ## Required
+
+  * `file_path` - The path to the N-PX document to parse
+  """
+  @spec npx_document_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def npx_document_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: npx_document_from_string(body)
+  end
+
+  @doc """
+  Parses a form N-PX document from a given url
+
+  ## Required
+
+  * `url` - The url of the N-PX document to parse
+  """
+  @spec npx_document_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def npx_document_from_url(url) do
+    with {:ok, body} <- get(url), do: npx_document_from_string(body)
+  end
+
+  @doc """
+  Parses a form N-PX primary document from a string
+
+  ## Required
+
+  * `xml_str` - The document xml string to parse
+  """
+  @spec npx_document_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
+  def npx_document_from_string(xml_str), do: EDGAR.Native.parse_npx_document(xml_str)
+
+  @doc """
+  Parses a form N-PX proxy vote table from a file
+
+  ## Required
+
+  * `file_path` - The path to the N-PX proxy vote table file to parse
+  """
+  @spec npx_table_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def npx_table_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: npx_table_from_string(body)
+  end
+
+  @doc """
+  Parses a form N-PX proxy vote table from a given url
+
+  ## Required
+
+  * `url` - The url of the N-PX proxy vote table file to parse
+  """
+  @spec npx_table_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def npx_table_from_url(url) do
+    with {:ok, body} <- get(url), do: npx_table_from_string(body)
+  end
+
+  @doc """
+  Parses a form N-PX proxy vote table from a string
+
+  ## Required
+
+  * `xml_str` - The proxy vote table xml string to parse
+  """
+  @spec npx_table_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
+  def npx_table_from_string(xml_str), do: EDGAR.Native.parse_npx_table(xml_str)
+
+  @doc """
+  Parses a schedule 13D, 13D/A, 13G, or 13G/A filing for a given CIK and accession number
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `accession_number` - The accession number of the filing
+  """
+  @spec schedule13_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+          success_type(map()) | error_type()
+  def schedule13_from_filing(cik, accession_number) do
+    case filing_index(cik, accession_number) do
+      {:ok, index} ->
+        types = ["SCHEDULE 13D", "SCHEDULE 13D/A", "SCHEDULE 13G", "SCHEDULE 13G/A"]
+
+        case find_index_document(index, types) do
+          nil ->
+            {:error, "No schedule 13D or 13G document found"}
+
+          document ->
+            acc_no = String.replace(accession_number, "-", "")
+            xml_file_url = "#{@edgar_archives_url}/data/#{cik}/#{acc_no}/#{document.name}"
+
+            schedule13_from_url(xml_file_url)
+        end
+
+      error ->
+        error
+    end
+  end
+
+  @doc """
+  Parses a schedule 13D or 13G document from a given file path
+
+  ## Required
+
+  * `file_path` - The path to the schedule 13D or 13G document to parse
+  """
+  @spec schedule13_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def schedule13_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: schedule13_from_string(body)
+  end
+
+  @doc """
+  Parses a schedule 13D or 13G document from a given url
+
+  ## Required
+
+  * `url` - The url of the schedule 13D or 13G document to parse
+  """
+  @spec schedule13_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def schedule13_from_url(url) do
+    with {:ok, body} <- get(url), do: schedule13_from_string(body)
+  end
+
+  @doc """
+  Parses a schedule 13D or 13G document from a string
+
+  ## Required
+
+  * `xml_str` - The schedule 13D or 13G xml string to parse
+  """
+  @spec schedule13_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
+  def schedule13_from_string(xml_str), do: EDGAR.Native.parse_schedule13(xml_str)
+
+  @doc """
+  Parses the FilingSummary.xml of an XBRL filing for a given CIK and accession number
+
+  ## Required
+
+  * `cik` - The CIK of the entity
+  * `accession_number` - The accession number of the filing
+  """
+  @spec filing_summary_from_filing(cik :: String.t(), accession_number :: String.t()) ::
+          success_type(map()) | error_type()
+  def filing_summary_from_filing(cik, accession_number) do
+    formatted_acc_no = String.replace(accession_number, "-", "")
+
+    filing_summary_from_url(
+      "#{@edgar_archives_url}/data/#{cik}/#{formatted_acc_no}/FilingSummary.xml"
+    )
+  end
+
+  @doc """
+  Parses a FilingSummary.xml from a given file path
+
+  ## Required
+
+  * `file_path` - The path to the FilingSummary.xml to parse
+  """
+  @spec filing_summary_from_file(file_path :: String.t()) :: success_type(map()) | error_type()
+  def filing_summary_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: filing_summary_from_string(body)
+  end
+
+  @doc """
+  Parses a FilingSummary.xml from a given url
+
+  ## Required
+
+  * `url` - The url of the FilingSummary.xml to parse
+  """
+  @spec filing_summary_from_url(url :: String.t()) :: success_type(map()) | error_type()
+  def filing_summary_from_url(url) do
+    with {:ok, body} <- get(url), do: filing_summary_from_string(body)
+  end
+
+  @doc """
+  Parses a FilingSummary.xml from a string
+
+  ## Required
+
+  * `xml_str` - The FilingSummary xml string to parse
+  """
+  @spec filing_summary_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
+  def filing_summary_from_string(xml_str), do: EDGAR.Native.parse_filing_summary(xml_str)
+
+  @doc """
+  Parses a xbrl presentation linkbase (`*_pre.xml`) from a given file path
+
+  ## Required
+
+  * `file_path` - The path to the presentation linkbase to parse
+  """
+  @spec presentation_linkbase_from_file(file_path :: String.t()) ::
+          success_type(list(map())) | error_type()
+  def presentation_linkbase_from_file(file_path) do
+    with {:ok, body} <- File.read(file_path), do: presentation_linkbase_from_string(body)
+  end
+
+  @doc """
+  Parses a xbrl presentation linkbase (`*_pre.xml`) from a given url
+
+  ## Required
+
+  * `url` - The url of the presentation linkbase to parse
+  """
+  @spec presentation_linkbase_from_url(url :: String.t()) ::
+          success_type(list(map())) | error_type()
+  def presentation_linkbase_from_url(url) do
+    with {:ok, body} <- get(url), do: presentation_linkbase_from_string(body)
+  end
+
+  @doc """
+  Parses a xbrl presentation linkbase into the concepts presented under each role
+
+  ## Required
+
+  * `xml_str` - The presentation linkbase xml string to parse
+  """
+  @spec presentation_linkbase_from_string(xml_str :: String.t()) ::
+          success_type(list(map())) | error_type()
+  def presentation_linkbase_from_string(xml_str),
+    do: EDGAR.Native.parse_presentation_linkbase(xml_str)
+
+  @doc """
+  Groups parsed xbrl facts under the FilingSummary report that presents them
+
+  Each report's role is looked up in the presentation linkbase, and the facts
+  whose concept is presented under that role are returned with the report.
+  Facts with dimensions are only returned when the role also presents each of
+  their axes and members.
+
+  ## Required
+
+  * `summary` - The parsed FilingSummary.xml
+  * `presentation` - The parsed presentation linkbase
+  * `xbrl` - The parsed xbrl instance
+  """
+  @spec xbrl_report_facts(summary :: map(), presentation :: list(map()), xbrl :: map()) ::
+          list(map())
+  def xbrl_report_facts(summary, presentation, xbrl),
+    do: EDGAR.Native.group_facts_by_report(summary, presentation, xbrl)
+
+  @doc """
+  Classifies the period of each parsed xbrl fact and assigns its fiscal year and quarter
+
+  Durations are classified as `quarter`, `half`, `nine_months`, `annual` or `other`, and
+  instants as `instant`. The fiscal year end is read from `dei:CurrentFiscalYearEndDate`
+  unless one is given, and fiscal years are aligned with `dei:DocumentFiscalYearFocus`.
+
+  ## Required
+
+  * `xbrl` - The parsed xbrl instance
+
+  ## Optional
+
+  * `fiscal_year_end` - The fiscal year end, e.g. `"--09-28"` or `"0928"`
+  """
+  @spec xbrl_fiscal_periods(xbrl :: map(), fiscal_year_end :: String.t() | nil) ::
+          success_type(list(map())) | error_type()
+  def xbrl_fiscal_periods(xbrl, fiscal_year_end \\ nil),
+    do: EDGAR.Native.classify_fiscal_periods(xbrl, fiscal_year_end)
+
+  @doc """
+  Derives missing quarters and trailing twelve month values from year-to-date facts
+
+  Quarters are the difference between consecutive year-to-date values (e.g. Q4 is
+  FY - 9M), and TTM is the prior fiscal year plus the current year-to-date value
+  less the prior year-to-date value. Only facts without dimensions are used, and
+  facts from several filings can be combined.
+
+  ## Required
+
+  * `fiscal_facts` - Facts returned by `xbrl_fiscal_periods/2`
+  """
+  @spec xbrl_derived_values(fiscal_facts :: list(map())) :: list(map())
+  def xbrl_derived_values(fiscal_facts), do: EDGAR.Native.derive_fiscal_values(fiscal_facts)
+
+  @doc """
+  Extracts the dei cover page of a parsed xbrl instance
+
+  Securities and shares outstanding are returned per class of stock, keyed by
+  their `StatementClassOfStockAxis` member (or `nil` when reported without a
+  dimension).
+
+  ## Required
+
+  * `xbrl` - The parsed xbrl instance
+  """
+  @spec xbrl_cover_page(xbrl :: map()) :: map()
+  def xbrl_cover_page(xbrl), do: EDGAR.Native.extract_cover_page(xbrl)
+
+  @doc """
+  Returns the default line items used by `xbrl_standardized_statements/2`
+
+  Each line item has a `name`, an ordered list of fallback `concepts` and an
+  ordered list of `formulas` tried when none of the concepts is reported.
+  """
+  @spec xbrl_default_line_items() :: list(map())
+  def xbrl_default_line_items(), do: EDGAR.Native.default_line_items()
+
+  @doc """
+  Resolves standardized line items (Revenue, NetIncome, TotalAssets, OperatingCashFlow...)
+  for every period of a parsed xbrl instance
+
+  Each line item is resolved from the first of its concepts reported for the period, or
+  else from the first of its formulas whose operands can all be resolved. Formulas add
+  and subtract other line items or concepts separated by whitespace, e.g.
+  `"Revenue - CostOfRevenue"`. Every value reports the `concept` or `formula` used and
+  the concepts it was computed from in `inputs`. Only facts without dimensions are used.
+
+  ## Required
+
+  * `xbrl` - The parsed xbrl instance
+
+  ## Optional
+
+  * `line_items` - A list of `%{name: _, concepts: [_], formulas: [_]}` maps, defaults
+    to `xbrl_default_line_items/0`
+  """
+  @spec xbrl_standardized_statements(xbrl :: map(), line_items :: list(map()) | nil) ::
+          success_type(list(map())) | error_type()
+  def xbrl_standardized_statements(xbrl, line_items \\ nil) do
+    line_items = line_items || EDGAR.Native.default_line_items()
+    EDGAR.Native.standardize_statements(xbrl, line_items)
+  end
+
+  @doc """
+  Returns the default ratios used by `xbrl_ratios/2`
+
+  Margins, current ratio, return on equity and assets, leverage and growth rates,
+  each as a `name` and an `expression` over the default standardized line items.
+  """
+  @spec xbrl_default_ratios() :: list(map())
+  def xbrl_default_ratios(), do: EDGAR.Native.default_ratios()
+
+  @doc """
+  Computes ratios for every period of a parsed xbrl instance
+
+  Expressions combine concepts and numbers with `+`, `-`, `*`, `/` and parentheses,
+  e.g. `"AssetsCurrent / LiabilitiesCurrent"`. Names of the line items such as `Revenue`
+  stand for their concepts and formulas, while prefixed names such as
+  `us-gaap:Revenues` always refer to a concept. `prior(x)` evaluates `x` for the same
+  period one year earlier and `coalesce(a, b, ...)` the first argument that can be
+  evaluated. Durations also use the instants at their end date, so ratios of a
+  duration and an instant (e.g. return on equity) use the closing balance and are not
+  annualized. Only facts without dimensions are used, and amounts are only added or
+  subtracted when their units match.
+
+  Each value returns its `unit` and the facts it was computed from in `inputs`.
+
+  ## Required
+
+  * `xbrl` - The parsed xbrl instance
+
+  ## Optional
+
+  * `ratios` - A list of `%{name: _, expression: _}` maps, defaults to
+    `xbrl_default_ratios/0`
+  * `line_items` - A list of `%{name: _, concepts: [_], formulas: [_]}` maps usable by name
+    in expressions, defaults to `xbrl_default_line_items/0`
+  """
+  @spec xbrl_ratios(
+          xbrl :: map(),
+          ratios :: list(map()) | nil,
+          line_items :: list(map()) | nil
+        ) :: success_type(list(map())) | error_type()
+  def xbrl_ratios(xbrl, ratios \\ nil, line_items \\ nil) do
+    ratios = ratios || EDGAR.Native.default_ratios()
+    line_items = line_items || EDGAR.Native.default_line_items()
+    EDGAR.Native.compute_ratios(xbrl, ratios, line_items)
+  end
+
   @doc """
   Parses a xbrl filing file from a given file path
 
@@ -1249,12 +2477,14 @@ defmodule EDGAR do
   @spec filing_feed_from_string(xml_str :: String.t()) :: success_type(map()) | error_type()
   def filing_feed_from_string(xml_str), do: EDGAR.Native.parse_filing_feed(xml_str)
 
-  defp get(url) do
+  defp get(url, opts \\ []) do
     SimpleRateLimiter.wait_and_proceed(fn ->
       user_agent =
         Application.get_env(:edgar_client, :user_agent, "default <default@default.com>")
 
-      case Req.get(url, headers: [{"User-Agent", user_agent}], redirect_log_level: false) do
+      req_opts = [headers: [{"User-Agent", user_agent}], redirect_log_level: false] ++ opts
+
+      case Req.get(url, req_opts) do
         {:ok, %Req.Response{status: 200, body: body}} ->
           {:ok, body}
 
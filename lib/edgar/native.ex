@@ -24,12 +24,42 @@ defmodule EDGAR.Native do
     mode: mode,
     force_build: System.get_env("EDGAR_CLIENT_BUILD") in ["1", "true"]
 
+  def check_form13f_consistency(_document, _table), do: :erlang.nif_error(:nif_not_loaded)
+  def classify_fiscal_periods(_document, _fiscal_year_end),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def compute_ratios(_document, _ratios, _line_items), do: :erlang.nif_error(:nif_not_loaded)
+  def default_line_items(), do: :erlang.nif_error(:nif_not_loaded)
+  def default_ratios(), do: :erlang.nif_error(:nif_not_loaded)
+  def derive_fiscal_values(_facts), do: :erlang.nif_error(:nif_not_loaded)
+  def extract_cover_page(_document), do: :erlang.nif_error(:nif_not_loaded)
+  def merge_ownership_amendments(_documents), do: :erlang.nif_error(:nif_not_loaded)
+  def normalize_company_facts(_json, _options), do: :erlang.nif_error(:nif_not_loaded)
+  def normalize_insider_transactions(_document), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_adv_feed(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def group_facts_by_report(_summary, _presentation, _document),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def parse_current_feed(_xml), do: :erlang.nif_error(:nif_not_loaded)
   def parse_company_feed(_xml), do: :erlang.nif_error(:nif_not_loaded)
   def parse_filing_feed(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_filing_index(_html), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_filing_index_headers(_html), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_filing_summary(_xml), do: :erlang.nif_error(:nif_not_loaded)
   def parse_form13f_document(_xml), do: :erlang.nif_error(:nif_not_loaded)
   def parse_form13f_table(_xml), do: :erlang.nif_error(:nif_not_loaded)
-  def parse_ownership_form(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_form144(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_formc(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_formd(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_nmfp(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_nport(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_npx_document(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_npx_table(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_ownership_form(_xml, _options), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_presentation_linkbase(_xml), do: :erlang.nif_error(:nif_not_loaded)
   def parse_rss_feed(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_schedule13(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def parse_submissions(_json, _filter), do: :erlang.nif_error(:nif_not_loaded)
   def parse_xbrl(_xml), do: :erlang.nif_error(:nif_not_loaded)
+  def standardize_statements(_document, _line_items), do: :erlang.nif_error(:nif_not_loaded)
 end
@@ -0,0 +1,310 @@
+use crate::xml::{parse_float64, parse_int64};
+use roxmltree::Document as XMLDoc;
+use rustler::NifMap;
+
+// https://adviserinfo.sec.gov/compilation
+// The IAPD compilation feed stores Form ADV answers as attributes named
+// after the item and question, e.g. Item5F/@Q5F2C for total regulatory AUM.
+
+#[derive(NifMap)]
+pub struct Feed {
+    generated_on: Option<String>,
+    firms: Vec<Firm>,
+}
+
+#[derive(NifMap)]
+pub struct Firm {
+    crd_number: String,
+    sec_number: Option<String>,
+    sec_region: Option<String>,
+    business_name: Option<String>,
+    legal_name: Option<String>,
+    main_address: Option<Address>,
+    mailing_address: Option<Address>,
+    registration: Option<Registration>,
+    filing_date: Option<String>,
+    form_version: Option<String>,
+    total_employees: Option<i64>,
+    number_of_clients: Option<String>,
+    number_of_clients_over_100: Option<i64>,
+    regulatory_aum: Option<RegulatoryAum>,
+    custody: Option<Custody>,
+    has_disciplinary_disclosures: Option<bool>,
+    disciplinary_flags: Vec<DisciplinaryFlag>,
+    direct_owners: Vec<Owner>,
+    indirect_owners: Vec<Owner>,
+}
+
+#[derive(NifMap)]
+pub struct Address {
+    street1: Option<String>,
+    street2: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    country: Option<String>,
+    postal_code: Option<String>,
+    phone_number: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct Registration {
+    firm_type: Option<String>,
+    status: Option<String>,
+    date: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct RegulatoryAum {
+    discretionary: Option<f64>,
+    non_discretionary: Option<f64>,
+    total: Option<f64>,
+    discretionary_accounts: Option<i64>,
+    non_discretionary_accounts: Option<i64>,
+    total_accounts: Option<i64>,
+}
+
+#[derive(NifMap)]
+pub struct Custody {
+    client_cash_or_bank_accounts: Option<bool>,
+    client_securities: Option<bool>,
+    related_person_cash_or_bank_accounts: Option<bool>,
+    related_person_securities: Option<bool>,
+}
+
+#[derive(NifMap)]
+pub struct DisciplinaryFlag {
+    question: String,
+    answer: bool,
+}
+
+#[derive(NifMap)]
+pub struct Owner {
+    name: String,
+    entity_type: Option<String>,
+    title_or_status: Option<String>,
+    status_date: Option<String>,
+    ownership_code: Option<String>,
+    is_control_person: Option<bool>,
+    is_public_reporting: Option<bool>,
+    crd_number: Option<String>,
+}
+
+#[rustler::nif]
+pub fn parse_adv_feed(xml: &str) -> Result<Feed, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+    let generated_on = get_attribute(&root_node, "GenOn");
+
+    let firms = root_node
+        .children()
+        .filter(|node| node.has_tag_name("Firms"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("Firm"))
+        .enumerate()
+        .map(|(index, firm_node)| {
+            parse_firm(&firm_node).map_err(|e| format!("Firm {}: {}", index + 1, e))
+        })
+        .collect::<Result<Vec<Firm>, String>>()?;
+
+    Ok(Feed {
+        generated_on,
+        firms,
+    })
+}
+
+fn parse_firm(node: &roxmltree::Node) -> Result<Firm, String> {
+    let info_node = find_child(node, "Info").ok_or("Info not found".to_string())?;
+    let crd_number =
+        get_attribute(&info_node, "FirmCrdNb").ok_or("missing attribute: FirmCrdNb".to_string())?;
+    let sec_number = get_attribute(&info_node, "SECNb");
+    let sec_region = get_attribute(&info_node, "SECRgnCD");
+    let business_name = get_attribute(&info_node, "BusNm");
+    let legal_name = get_attribute(&info_node, "LegalNm");
+    let main_address = find_child(node, "MainAddr").map(|node| parse_address(&node));
+    let mailing_address = find_child(node, "MailingAddr").map(|node| parse_address(&node));
+    let registration = find_child(node, "Rgstn").map(|node| Registration {
+        firm_type: get_attribute(&node, "FirmType"),
+        status: get_attribute(&node, "St"),
+        date: get_attribute(&node, "Dt"),
+    });
+    let filing_node = find_child(node, "Filing");
+    let filing_date = filing_node.and_then(|node| get_attribute(&node, "Dt"));
+    let form_version = filing_node.and_then(|node| get_attribute(&node, "FormVrsn"));
+
+    let part_1a_node = node
+        .children()
+        .find(|node| node.has_tag_name("FormInfo"))
+        .and_then(|node| find_child(&node, "Part1A"));
+
+    let employees_node = part_1a_node.and_then(|node| find_child(&node, "Item5A"));
+    let total_employees = employees_node
+        .map(|node| get_int64_attribute(&node, "TtlEmp"))
+        .transpose()?
+        .flatten();
+    let clients_node = part_1a_node.and_then(|node| find_child(&node, "Item5C"));
+    let number_of_clients = clients_node.and_then(|node| get_attribute(&node, "Q5C1"));
+    let number_of_clients_over_100 = clients_node
+        .map(|node| get_int64_attribute(&node, "Q5C2"))
+        .transpose()?
+        .flatten();
+    let regulatory_aum = part_1a_node
+        .and_then(|node| find_child(&node, "Item5F"))
+        .map(|node| parse_regulatory_aum(&node))
+        .transpose()?;
+    let custody = part_1a_node.and_then(|node| parse_custody(&node));
+    let has_disciplinary_disclosures = part_1a_node
+        .and_then(|node| find_child(&node, "Item11"))
+        .and_then(|node| get_bool_attribute(&node, "Q11"));
+    let disciplinary_flags = part_1a_node
+        .map(|node| parse_disciplinary_flags(&node))
+        .unwrap_or_default();
+
+    let direct_owners = parse_owners(node, "ScheduleA")?;
+    let indirect_owners = parse_owners(node, "ScheduleB")?;
+
+    Ok(Firm {
+        crd_number,
+        sec_number,
+        sec_region,
+        business_name,
+        legal_name,
+        main_address,
+        mailing_address,
+        registration,
+        filing_date,
+        form_version,
+        total_employees,
+        number_of_clients,
+        number_of_clients_over_100,
+        regulatory_aum,
+        custody,
+        has_disciplinary_disclosures,
+        disciplinary_flags,
+        direct_owners,
+        indirect_owners,
+    })
+}
+
+fn parse_address(node: &roxmltree::Node) -> Address {
+    Address {
+        street1: get_attribute(node, "Strt1"),
+        street2: get_attribute(node, "Strt2"),
+        city: get_attribute(node, "City"),
+        state: get_attribute(node, "State"),
+        country: get_attribute(node, "Cntry"),
+        postal_code: get_attribute(node, "PostlCd"),
+        phone_number: get_attribute(node, "PhNb"),
+    }
+}
+
+fn parse_regulatory_aum(node: &roxmltree::Node) -> Result<RegulatoryAum, String> {
+    Ok(RegulatoryAum {
+        discretionary: get_float64_attribute(node, "Q5F2A")?,
+        non_discretionary: get_float64_attribute(node, "Q5F2B")?,
+        total: get_float64_attribute(node, "Q5F2C")?,
+        discretionary_accounts: get_int64_attribute(node, "Q5F2D")?,
+        non_discretionary_accounts: get_int64_attribute(node, "Q5F2E")?,
+        total_accounts: get_int64_attribute(node, "Q5F2F")?,
+    })
+}
+
+fn parse_custody(node: &roxmltree::Node) -> Option<Custody> {
+    let advisory_node = find_child(node, "Item9A");
+    let related_person_node = find_child(node, "Item9B");
+
+    if advisory_node.is_none() && related_person_node.is_none() {
+        return None;
+    }
+
+    Some(Custody {
+        client_cash_or_bank_accounts: advisory_node
+            .and_then(|node| get_bool_attribute(&node, "Q9A1A")),
+        client_securities: advisory_node.and_then(|node| get_bool_attribute(&node, "Q9A1B")),
+        related_person_cash_or_bank_accounts: related_person_node
+            .and_then(|node| get_bool_attribute(&node, "Q9B1A")),
+        related_person_securities: related_person_node
+            .and_then(|node| get_bool_attribute(&node, "Q9B1B")),
+    })
+}
+
+// Item 11 is split across Item11A..Item11H, one attribute per question.
+fn parse_disciplinary_flags(node: &roxmltree::Node) -> Vec<DisciplinaryFlag> {
+    node.children()
+        .filter(|node| {
+            let name = node.tag_name().name();
+            node.is_element() && name.starts_with("Item11") && name != "Item11"
+        })
+        .flat_map(|node| node.attributes().to_vec())
+        .filter_map(|attribute| {
+            parse_bool(attribute.value()).map(|answer| DisciplinaryFlag {
+                question: attribute.name().to_string(),
+                answer,
+            })
+        })
+        .collect()
+}
+
+fn parse_owners(node: &roxmltree::Node, tag: &str) -> Result<Vec<Owner>, String> {
+    node.descendants()
+        .filter(|node| node.has_tag_name(tag))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("Owner"))
+        .enumerate()
+        .map(|(index, owner_node)| {
+            let name = get_attribute(&owner_node, "FullLegalNm").ok_or(format!(
+                "{} Owner {}: missing attribute: FullLegalNm",
+                tag,
+                index + 1
+            ))?;
+
+            Ok(Owner {
+                name,
+                entity_type: get_attribute(&owner_node, "DEFlag"),
+                title_or_status: get_attribute(&owner_node, "TitleStatus"),
+                status_date: get_attribute(&owner_node, "StatusDt"),
+                ownership_code: get_attribute(&owner_node, "OwnershipCode"),
+                is_control_person: get_bool_attribute(&owner_node, "ControlPerson"),
+                is_public_reporting: get_bool_attribute(&owner_node, "PublicReporting"),
+                crd_number: get_attribute(&owner_node, "CRDNb"),
+            })
+        })
+        .collect()
+}
+
+fn find_child<'a, 'input>(
+    node: &roxmltree::Node<'a, 'input>,
+    tag: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children().find(|node| node.has_tag_name(tag))
+}
+
+fn get_attribute(node: &roxmltree::Node, attribute: &str) -> Option<String> {
+    node.attribute(attribute)
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
+fn get_int64_attribute(node: &roxmltree::Node, attribute: &str) -> Result<Option<i64>, String> {
+    get_attribute(node, attribute)
+        .map(|value| parse_int64(&value, attribute))
+        .transpose()
+}
+
+fn get_float64_attribute(node: &roxmltree::Node, attribute: &str) -> Result<Option<f64>, String> {
+    get_attribute(node, attribute)
+        .map(|value| parse_float64(&value, attribute))
+        .transpose()
+}
+
+fn get_bool_attribute(node: &roxmltree::Node, attribute: &str) -> Option<bool> {
+    node.attribute(attribute).and_then(parse_bool)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_uppercase().as_str() {
+        "Y" | "1" | "TRUE" => Some(true),
+        "N" | "0" | "FALSE" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,273 @@
+use rustler::NifMap;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+// https://www.sec.gov/edgar/sec-api-documentation
+// companyfacts nests facts as facts -> taxonomy -> concept -> units -> [..],
+// companyconcept is a single concept with its units at the top level, and
+// frames is a single concept, unit and period with one fact per company.
+
+#[derive(NifMap)]
+pub struct FactTable {
+    cik: Option<i64>,
+    entity_name: Option<String>,
+    facts: Vec<FactRow>,
+}
+
+#[derive(NifMap)]
+pub struct FactRow {
+    cik: Option<i64>,
+    entity_name: Option<String>,
+    taxonomy: String,
+    concept: String,
+    label: Option<String>,
+    unit: String,
+    period_type: String,
+    start: Option<String>,
+    end: String,
+    value: f64,
+    accn: Option<String>,
+    fy: Option<i64>,
+    fp: Option<String>,
+    form: Option<String>,
+    filed: Option<String>,
+    frame: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct FactTableOptions {
+    concepts: Option<Vec<String>>,
+    dedupe_frames: bool,
+}
+
+impl FactTableOptions {
+    // Concepts can be given either as "Revenues" or as "us-gaap:Revenues".
+    fn includes(&self, taxonomy: &str, concept: &str) -> bool {
+        self.concepts
+            .as_ref()
+            .map(|concepts| {
+                concepts.iter().any(|name| match name.split_once(':') {
+                    Some((prefix, name)) => prefix == taxonomy && name == concept,
+                    None => name == concept,
+                })
+            })
+            .unwrap_or(true)
+    }
+}
+
+struct Concept<'a> {
+    taxonomy: &'a str,
+    concept: &'a str,
+    label: Option<String>,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn normalize_company_facts(json: &str, options: FactTableOptions) -> Result<FactTable, String> {
+    let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let root = value
+        .as_object()
+        .ok_or("expected a json object".to_string())?;
+    let cik = get_int64(root, "cik")?;
+    let entity_name = get_string(root, "entityName");
+
+    let mut facts = vec![];
+
+    if let Some(taxonomies) = root.get("facts").and_then(|facts| facts.as_object()) {
+        // companyfacts
+        for (taxonomy, concepts) in taxonomies {
+            let concepts = concepts
+                .as_object()
+                .ok_or(format!("{}: expected a json object", taxonomy))?;
+
+            for (concept, concept_value) in concepts {
+                if !options.includes(taxonomy, concept) {
+                    continue;
+                }
+                let concept_object = concept_value
+                    .as_object()
+                    .ok_or(format!("{}:{}: expected a json object", taxonomy, concept))?;
+                let concept = Concept {
+                    taxonomy,
+                    concept,
+                    label: get_string(concept_object, "label"),
+                };
+
+                parse_units(concept_object, &concept, cik, &entity_name, &mut facts)?;
+            }
+        }
+    } else if root.contains_key("units") {
+        // companyconcept
+        let concept = parse_concept(root)?;
+
+        if options.includes(concept.taxonomy, concept.concept) {
+            parse_units(root, &concept, cik, &entity_name, &mut facts)?;
+        }
+    } else if let Some(data) = root.get("data").and_then(|data| data.as_array()) {
+        // frames
+        let concept = parse_concept(root)?;
+        let unit = get_string(root, "uom").ok_or("missing uom".to_string())?;
+        let frame = get_string(root, "ccp");
+
+        if options.includes(concept.taxonomy, concept.concept) {
+            for (index, entry) in data.iter().enumerate() {
+                let entry = entry
+                    .as_object()
+                    .ok_or(format!("data {}: expected a json object", index + 1))?;
+                let entity_name = get_string(entry, "entityName");
+                let mut row = get_int64(entry, "cik")
+                    .and_then(|cik| parse_fact(entry, &concept, &unit, cik, &entity_name))
+                    .map_err(|e| format!("data {}: {}", index + 1, e))?;
+                row.frame = row.frame.or(frame.clone());
+                facts.push(row);
+            }
+        }
+    } else {
+        return Err("unrecognized json: expected facts, units or data".to_string());
+    }
+
+    if options.dedupe_frames {
+        facts = dedupe_frames(facts);
+    }
+
+    Ok(FactTable {
+        cik,
+        entity_name,
+        facts,
+    })
+}
+
+fn parse_concept(root: &Map<String, Value>) -> Result<Concept<'_>, String> {
+    Ok(Concept {
+        taxonomy: root
+            .get("taxonomy")
+            .and_then(|value| value.as_str())
+            .ok_or("missing taxonomy".to_string())?,
+        concept: root
+            .get("tag")
+            .and_then(|value| value.as_str())
+            .ok_or("missing tag".to_string())?,
+        label: get_string(root, "label"),
+    })
+}
+
+fn parse_units(
+    object: &Map<String, Value>,
+    concept: &Concept,
+    cik: Option<i64>,
+    entity_name: &Option<String>,
+    facts: &mut Vec<FactRow>,
+) -> Result<(), String> {
+    let units = object
+        .get("units")
+        .and_then(|units| units.as_object())
+        .ok_or(format!(
+            "{}:{}: missing units",
+            concept.taxonomy, concept.concept
+        ))?;
+
+    for (unit, entries) in units {
+        let entries = entries.as_array().ok_or(format!(
+            "{}:{} {}: expected an array",
+            concept.taxonomy, concept.concept, unit
+        ))?;
+
+        for (index, entry) in entries.iter().enumerate() {
+            let row = entry
+                .as_object()
+                .ok_or("expected a json object".to_string())
+                .and_then(|entry| parse_fact(entry, concept, unit, cik, entity_name))
+                .map_err(|e| {
+                    format!(
+                        "{}:{} {} {}: {}",
+                        concept.taxonomy,
+                        concept.concept,
+                        unit,
+                        index + 1,
+                        e
+                    )
+                })?;
+            facts.push(row);
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_fact(
+    entry: &Map<String, Value>,
+    concept: &Concept,
+    unit: &str,
+    cik: Option<i64>,
+    entity_name: &Option<String>,
+) -> Result<FactRow, String> {
+    let start = get_string(entry, "start");
+    let end = get_string(entry, "end").ok_or("missing end".to_string())?;
+    let value = entry
+        .get("val")
+        .and_then(|value| value.as_f64())
+        .ok_or("missing val".to_string())?;
+    let period_type = match start {
+        Some(_) => "duration",
+        None => "instant",
+    };
+
+    Ok(FactRow {
+        cik,
+        entity_name: entity_name.clone(),
+        taxonomy: concept.taxonomy.to_string(),
+        concept: concept.concept.to_string(),
+        label: concept.label.clone(),
+        unit: unit.to_string(),
+        period_type: period_type.to_string(),
+        start,
+        end,
+        value,
+        accn: get_string(entry, "accn"),
+        fy: get_int64(entry, "fy")?,
+        fp: get_string(entry, "fp"),
+        form: get_string(entry, "form"),
+        filed: get_string(entry, "filed"),
+        frame: get_string(entry, "frame"),
+    })
+}
+
+// The same fact is repeated by every filing that reports it; the API marks
+// the one that best represents each calendar period with a frame, so keep
+// only the first framed fact per concept, unit and frame.
+fn dedupe_frames(facts: Vec<FactRow>) -> Vec<FactRow> {
+    let mut seen = HashSet::new();
+
+    facts
+        .into_iter()
+        .filter(|fact| match &fact.frame {
+            Some(frame) => seen.insert((
+                fact.cik,
+                fact.taxonomy.clone(),
+                fact.concept.clone(),
+                fact.unit.clone(),
+                frame.clone(),
+            )),
+            None => false,
+        })
+        .collect()
+}
+
+fn get_string(object: &Map<String, Value>, key: &str) -> Option<String> {
+    match object.get(key)? {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn get_int64(object: &Map<String, Value>, key: &str) -> Result<Option<i64>, String> {
+    match object.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(n)) => n
+            .as_i64()
+            .map(Some)
+            .ok_or(format!("invalid {}: {}", key, n)),
+        Some(Value::String(s)) => crate::xml::parse_int64(s.trim(), key).map(Some),
+        Some(other) => Err(format!("invalid {}: {}", key, other)),
+    }
+}
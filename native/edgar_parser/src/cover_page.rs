@@ -0,0 +1,248 @@
+use crate::xbrl::{Document, Fact};
+use crate::xml::Value;
+use rustler::NifMap;
+
+// https://xbrl.sec.gov/dei/
+// Cover page facts are reported without dimensions, except for the per
+// security facts (title, trading symbol, exchange) and the shares
+// outstanding, which are reported per class of stock when a registrant has
+// more than one, e.g. us-gaap:StatementClassOfStockAxis=ClassAMember.
+
+#[derive(NifMap)]
+pub struct CoverPage {
+    document_type: Option<String>,
+    document_period_end_date: Option<String>,
+    is_annual_report: Option<bool>,
+    is_quarterly_report: Option<bool>,
+    is_transition_report: Option<bool>,
+    is_amendment: Option<bool>,
+    amendment_description: Option<String>,
+    fiscal_year_focus: Option<i64>,
+    fiscal_period_focus: Option<String>,
+    current_fiscal_year_end_date: Option<String>,
+    registrant_name: Option<String>,
+    central_index_key: Option<String>,
+    file_number: Option<String>,
+    incorporation_state: Option<String>,
+    tax_identification_number: Option<String>,
+    address: Option<Address>,
+    phone_number: Option<String>,
+    filer_category: Option<String>,
+    is_well_known_seasoned_issuer: Option<bool>,
+    is_voluntary_filer: Option<bool>,
+    current_reporting_status: Option<bool>,
+    is_interactive_data_current: Option<bool>,
+    is_shell_company: Option<bool>,
+    is_small_business: Option<bool>,
+    is_emerging_growth_company: Option<bool>,
+    public_float: Option<f64>,
+    securities: Vec<Security>,
+    shares_outstanding: Vec<SharesOutstanding>,
+}
+
+#[derive(NifMap)]
+pub struct Address {
+    line1: Option<String>,
+    line2: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    postal_code: Option<String>,
+    country: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct Security {
+    class_member: Option<String>,
+    title: Option<String>,
+    trading_symbol: Option<String>,
+    exchange: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SharesOutstanding {
+    class_member: Option<String>,
+    date: Option<String>,
+    shares: f64,
+}
+
+#[rustler::nif]
+pub fn extract_cover_page(document: Document) -> CoverPage {
+    let facts = &document.facts;
+
+    let address = Address {
+        line1: get_text(facts, "EntityAddressAddressLine1"),
+        line2: get_text(facts, "EntityAddressAddressLine2"),
+        city: get_text(facts, "EntityAddressCityOrTown"),
+        state: get_text(facts, "EntityAddressStateOrProvince"),
+        postal_code: get_text(facts, "EntityAddressPostalZipCode"),
+        country: get_text(facts, "EntityAddressCountry"),
+    };
+    let has_address = address.line1.is_some() || address.city.is_some();
+    let phone_number = match (
+        get_text(facts, "CityAreaCode"),
+        get_text(facts, "LocalPhoneNumber"),
+    ) {
+        (Some(area_code), Some(number)) => Some(format!(
+            "({}) {}",
+            area_code.trim_matches(|c| c == '(' || c == ')'),
+            number
+        )),
+        (None, number) => number,
+        (area_code, None) => area_code,
+    };
+
+    CoverPage {
+        document_type: get_text(facts, "DocumentType"),
+        document_period_end_date: get_text(facts, "DocumentPeriodEndDate"),
+        is_annual_report: get_bool(facts, "DocumentAnnualReport"),
+        is_quarterly_report: get_bool(facts, "DocumentQuarterlyReport"),
+        is_transition_report: get_bool(facts, "DocumentTransitionReport"),
+        is_amendment: get_bool(facts, "AmendmentFlag"),
+        amendment_description: get_text(facts, "AmendmentDescription"),
+        fiscal_year_focus: get_int(facts, "DocumentFiscalYearFocus"),
+        fiscal_period_focus: get_text(facts, "DocumentFiscalPeriodFocus"),
+        current_fiscal_year_end_date: get_text(facts, "CurrentFiscalYearEndDate"),
+        registrant_name: get_text(facts, "EntityRegistrantName"),
+        central_index_key: get_cik(facts),
+        file_number: get_text(facts, "EntityFileNumber"),
+        incorporation_state: get_text(facts, "EntityIncorporationStateCountryCode"),
+        tax_identification_number: get_text(facts, "EntityTaxIdentificationNumber"),
+        address: Some(address).filter(|_| has_address),
+        phone_number,
+        filer_category: get_text(facts, "EntityFilerCategory"),
+        is_well_known_seasoned_issuer: get_bool(facts, "EntityWellKnownSeasonedIssuer"),
+        is_voluntary_filer: get_bool(facts, "EntityVoluntaryFilers"),
+        current_reporting_status: get_bool(facts, "EntityCurrentReportingStatus"),
+        is_interactive_data_current: get_bool(facts, "EntityInteractiveDataCurrent"),
+        is_shell_company: get_bool(facts, "EntityShellCompany"),
+        is_small_business: get_bool(facts, "EntitySmallBusiness"),
+        is_emerging_growth_company: get_bool(facts, "EntityEmergingGrowthCompany"),
+        public_float: entity_fact(facts, "EntityPublicFloat").and_then(|fact| fact.numeric_value()),
+        securities: parse_securities(facts),
+        shares_outstanding: parse_shares_outstanding(facts),
+    }
+}
+
+// Securities are grouped by their class of stock member; a registrant with a
+// single class usually reports them without a dimension.
+fn parse_securities(facts: &[Fact]) -> Vec<Security> {
+    let mut securities: Vec<Security> = vec![];
+
+    for fact in facts.iter().filter(|fact| {
+        matches!(
+            fact.concept.as_str(),
+            "Security12bTitle" | "TradingSymbol" | "SecurityExchangeName"
+        )
+    }) {
+        let Some(class_member) = class_member(fact) else {
+            continue;
+        };
+        let value = value_to_string(&fact.value);
+
+        let index = match securities
+            .iter()
+            .position(|security| security.class_member == class_member)
+        {
+            Some(index) => index,
+            None => {
+                securities.push(Security {
+                    class_member: class_member.clone(),
+                    title: None,
+                    trading_symbol: None,
+                    exchange: None,
+                });
+                securities.len() - 1
+            }
+        };
+        let security = &mut securities[index];
+
+        match fact.concept.as_str() {
+            "Security12bTitle" => security.title = security.title.take().or(value),
+            "TradingSymbol" => security.trading_symbol = security.trading_symbol.take().or(value),
+            _ => security.exchange = security.exchange.take().or(value),
+        }
+    }
+
+    // A dimensionless TradingSymbol next to per-class securities is the
+    // registrant's primary symbol rather than a security of its own.
+    if securities.len() > 1 {
+        securities.retain(|security| security.class_member.is_some() || security.title.is_some());
+    }
+
+    securities
+}
+
+fn parse_shares_outstanding(facts: &[Fact]) -> Vec<SharesOutstanding> {
+    facts
+        .iter()
+        .filter(|fact| fact.concept == "EntityCommonStockSharesOutstanding")
+        .filter_map(|fact| {
+            Some(SharesOutstanding {
+                class_member: class_member(fact)?,
+                date: fact.context.period.instant.clone(),
+                shares: fact.numeric_value()?,
+            })
+        })
+        .collect()
+}
+
+// Returns the class of stock member for a fact, None for a fact without
+// dimensions, or nothing for facts with any other dimension (e.g. the
+// LegalEntityAxis used by co-registrants).
+fn class_member(fact: &Fact) -> Option<Option<String>> {
+    match fact.context.segments.as_slice() {
+        [] => Some(None),
+        [segment] if segment.dimension == "StatementClassOfStockAxis" => {
+            Some(Some(segment.member.clone()))
+        }
+        _ => None,
+    }
+}
+
+fn entity_fact<'a>(facts: &'a [Fact], concept: &str) -> Option<&'a Fact> {
+    facts
+        .iter()
+        .find(|fact| fact.concept == concept && fact.context.segments.is_empty())
+}
+
+fn get_text(facts: &[Fact], concept: &str) -> Option<String> {
+    entity_fact(facts, concept).and_then(|fact| value_to_string(&fact.value))
+}
+
+fn get_int(facts: &[Fact], concept: &str) -> Option<i64> {
+    entity_fact(facts, concept).and_then(|fact| match &fact.value {
+        Value::Int(value) => Some(*value),
+        _ => None,
+    })
+}
+
+// The CIK is parsed as a number, so restore its zero padding.
+fn get_cik(facts: &[Fact]) -> Option<String> {
+    entity_fact(facts, "EntityCentralIndexKey").and_then(|fact| match &fact.value {
+        Value::Int(value) => Some(format!("{:010}", value)),
+        value => value_to_string(value),
+    })
+}
+
+fn get_bool(facts: &[Fact], concept: &str) -> Option<bool> {
+    entity_fact(facts, concept).and_then(|fact| match &fact.value {
+        Value::Bool(value) => Some(*value),
+        Value::Text(text) => match text.trim().to_lowercase().as_str() {
+            "yes" | "true" => Some(true),
+            "no" | "false" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::Int(value) => value.to_string(),
+        Value::Float(value) => value.to_string(),
+        Value::Text(text) => text.trim().to_string(),
+        Value::Bool(value) => value.to_string(),
+    };
+
+    Some(text).filter(|text| !text.is_empty())
+}
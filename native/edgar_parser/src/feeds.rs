@@ -1,4 +1,4 @@
-use crate::xml::{get_int32, get_string};
+use crate::xml::{get_int32, get_string, parse_int64};
 use roxmltree::Document as XMLDoc;
 use rustler::NifMap;
 
@@ -404,16 +404,16 @@ fn parse_links(node: &roxmltree::Node) -> Result<Vec<Link>, String> {
     let links = node
         .children()
         .filter(|node| node.has_tag_name("link"))
-        .filter_map(|link_node| {
+        .map(|link_node| {
             let href = link_node.attribute("href").map(|s| s.to_string());
             let rel = link_node.attribute("rel").map(|s| s.to_string());
             let link_type = link_node.attribute("type").map(|s| s.to_string());
 
-            Some(Link {
+            Link {
                 href,
                 rel,
                 link_type,
-            })
+            }
         })
         .collect();
     Ok(links)
@@ -476,7 +476,7 @@ pub struct FilingItem {
 #[derive(NifMap)]
 pub struct Enclosure {
     url: Option<String>,
-    length: Option<i32>,
+    length: Option<i64>,
     mime_type: Option<String>,
 }
 
@@ -501,7 +501,7 @@ pub struct File {
     sequence: Option<i32>,
     file: Option<String>,
     file_type: Option<String>,
-    size: Option<i32>,
+    size: Option<i64>,
     description: Option<String>,
     url: Option<String>,
 }
@@ -562,7 +562,8 @@ fn parse_enclosure(node: &roxmltree::Node) -> Result<Option<Enclosure>, String>
             let url = enclosure_node.attribute("url").map(|s| s.to_string());
             let length = enclosure_node
                 .attribute("length")
-                .and_then(|s| s.parse::<i32>().ok());
+                .map(|s| parse_int64(s, "length"))
+                .transpose()?;
             let mime_type = enclosure_node.attribute("type").map(|s| s.to_string());
 
             Ok(Enclosure {
@@ -617,7 +618,7 @@ fn parse_files(node: &roxmltree::Node) -> Result<Vec<File>, String> {
         .filter(|node| node.has_tag_name("xbrlFiles"))
         .flat_map(|node| node.children())
         .filter(|node| node.has_tag_name("xbrlFile"))
-        .filter_map(|file_node| {
+        .map(|file_node| {
             let sequence = file_node
                 .attribute((ns, "sequence"))
                 .and_then(|s| s.parse::<i32>().ok());
@@ -625,13 +626,14 @@ fn parse_files(node: &roxmltree::Node) -> Result<Vec<File>, String> {
             let file_type = file_node.attribute((ns, "type")).map(|s| s.to_string());
             let size = file_node
                 .attribute((ns, "size"))
-                .and_then(|s| s.parse::<i32>().ok());
+                .map(|s| parse_int64(s, "size"))
+                .transpose()?;
             let description = file_node
                 .attribute((ns, "description"))
                 .map(|s| s.to_string());
             let url = file_node.attribute((ns, "url")).map(|s| s.to_string());
 
-            Some(File {
+            Ok(File {
                 sequence,
                 file,
                 file_type,
@@ -640,6 +642,6 @@ fn parse_files(node: &roxmltree::Node) -> Result<Vec<File>, String> {
                 url,
             })
         })
-        .collect();
+        .collect::<Result<Vec<File>, String>>()?;
     Ok(files)
 }
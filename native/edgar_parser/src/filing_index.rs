@@ -0,0 +1,443 @@
+use crate::xml::parse_int64;
+use rustler::NifMap;
+
+// The filing index pages (`<accession>-index.htm` and
+// `<accession>-index-headers.html`) are HTML rather than well formed XML,
+// so they are scanned as text instead of going through roxmltree.
+
+#[derive(NifMap)]
+pub struct FilingIndex {
+    header: FilingHeader,
+    documents: Vec<IndexDocument>,
+}
+
+#[derive(NifMap)]
+pub struct FilingHeader {
+    accession_number: Option<String>,
+    form_type: Option<String>,
+    filing_date: Option<String>,
+    accepted: Option<String>,
+    period_of_report: Option<String>,
+    document_count: Option<i64>,
+    entities: Vec<HeaderEntity>,
+}
+
+#[derive(NifMap)]
+pub struct HeaderEntity {
+    role: Option<String>,
+    name: Option<String>,
+    cik: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct IndexDocument {
+    sequence: Option<i64>,
+    description: Option<String>,
+    name: String,
+    document_type: Option<String>,
+    size: Option<i64>,
+    href: Option<String>,
+}
+
+#[rustler::nif]
+pub fn parse_filing_index(html: &str) -> Result<FilingIndex, String> {
+    let lower = html.to_ascii_lowercase();
+
+    let form_type = find_element(html, &lower, "id=\"formname\"")
+        .and_then(|content| {
+            let lower_content = content.to_ascii_lowercase();
+            find_tag_content(&content, &lower_content, "strong")
+        })
+        .map(|s| strip_tags(&s))
+        .map(|s| s.strip_prefix("Form ").map(|s| s.to_string()).unwrap_or(s));
+    let accession_number = find_element(html, &lower, "id=\"secnum\"")
+        .map(|s| strip_tags(&s))
+        .and_then(|s| s.split_whitespace().last().map(|s| s.to_string()));
+
+    let info = parse_info_pairs(html, &lower);
+    let get_info = |key: &str| {
+        info.iter()
+            .find(|(head, _)| head.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.clone())
+    };
+    let document_count = get_info("Documents")
+        .map(|s| parse_int64(&s, "Documents"))
+        .transpose()?;
+
+    let entities = find_all_elements(html, &lower, "class=\"companyname\"")
+        .into_iter()
+        .map(|content| parse_company_name(&strip_tags(&content)))
+        .collect();
+
+    let header = FilingHeader {
+        accession_number,
+        form_type,
+        filing_date: get_info("Filing Date"),
+        accepted: get_info("Accepted"),
+        period_of_report: get_info("Period of Report"),
+        document_count,
+        entities,
+    };
+    let documents = parse_document_tables(html, &lower)?;
+
+    Ok(FilingIndex { header, documents })
+}
+
+#[rustler::nif]
+pub fn parse_filing_index_headers(html: &str) -> Result<FilingIndex, String> {
+    let lower = html.to_ascii_lowercase();
+    let blocks = find_all_tag_contents(html, &lower, "pre");
+    if blocks.is_empty() {
+        return Err("pre not found".to_string());
+    }
+    let text = blocks
+        .iter()
+        .map(|block| strip_tags_keep_lines(block))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut header = FilingHeader {
+        accession_number: None,
+        form_type: None,
+        filing_date: None,
+        accepted: None,
+        period_of_report: None,
+        document_count: None,
+        entities: vec![],
+    };
+    let mut documents = vec![];
+    let mut sections: Vec<String> = vec![];
+    let mut document: Option<IndexDocument> = None;
+
+    for line in text.lines().map(|line| line.trim()) {
+        let Some((tag, value)) = parse_sgml_line(line) else {
+            continue;
+        };
+
+        if let Some(closing) = tag.strip_prefix('/') {
+            if closing == "DOCUMENT" {
+                documents.extend(document.take());
+            }
+            if let Some(index) = sections.iter().rposition(|section| section == closing) {
+                sections.truncate(index);
+            }
+            continue;
+        }
+
+        if value.is_empty() {
+            match tag {
+                "DOCUMENT" => {
+                    document = Some(IndexDocument {
+                        sequence: None,
+                        description: None,
+                        name: String::new(),
+                        document_type: None,
+                        size: None,
+                        href: None,
+                    })
+                }
+                _ => {
+                    if let Some(role) = entity_role(tag) {
+                        header.entities.push(HeaderEntity {
+                            role: Some(role.to_string()),
+                            name: None,
+                            cik: None,
+                        })
+                    }
+                }
+            }
+            sections.push(tag.to_string());
+            continue;
+        }
+
+        if let Some(document) = document.as_mut() {
+            match tag {
+                "TYPE" => document.document_type = Some(value.to_string()),
+                "SEQUENCE" => document.sequence = Some(parse_int64(value, "SEQUENCE")?),
+                "FILENAME" => document.name = value.to_string(),
+                "DESCRIPTION" => document.description = Some(value.to_string()),
+                _ => {}
+            }
+            continue;
+        }
+
+        if sections.is_empty() {
+            match tag {
+                "ACCESSION-NUMBER" => header.accession_number = Some(value.to_string()),
+                "TYPE" => header.form_type = Some(value.to_string()),
+                "FILING-DATE" => header.filing_date = Some(format_date(value)),
+                "ACCEPTANCE-DATETIME" => header.accepted = Some(format_datetime(value)),
+                "PERIOD" => header.period_of_report = Some(format_date(value)),
+                "PUBLIC-DOCUMENT-COUNT" => {
+                    header.document_count = Some(parse_int64(value, "PUBLIC-DOCUMENT-COUNT")?)
+                }
+                _ => {}
+            }
+        } else if let Some(entity) = header.entities.last_mut() {
+            match tag {
+                "CONFORMED-NAME" if entity.name.is_none() => entity.name = Some(value.to_string()),
+                "CIK" if entity.cik.is_none() => entity.cik = Some(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(FilingIndex { header, documents })
+}
+
+// Use the same role names as the companyName blocks on -index.htm.
+fn entity_role(tag: &str) -> Option<&'static str> {
+    match tag {
+        "FILER" => Some("Filer"),
+        "REPORTING-OWNER" => Some("Reporting"),
+        "ISSUER" => Some("Issuer"),
+        "SUBJECT-COMPANY" => Some("Subject"),
+        "FILED-BY" => Some("Filed by"),
+        _ => None,
+    }
+}
+
+fn parse_sgml_line(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix('<')?;
+    let (tag, value) = rest.split_once('>')?;
+    Some((tag.trim(), value.trim()))
+}
+
+fn parse_info_pairs(html: &str, lower: &str) -> Vec<(String, String)> {
+    let heads = find_all_elements(html, lower, "class=\"infohead\"");
+    let values = find_all_elements(html, lower, "class=\"info\"");
+
+    heads
+        .into_iter()
+        .zip(values)
+        .map(|(head, value)| (strip_tags(&head), strip_tags(&value)))
+        .collect()
+}
+
+// Company blocks read like "Apple Inc. (Issuer) CIK: 0000320193 (see all
+// company filings)".
+fn parse_company_name(text: &str) -> HeaderEntity {
+    let (name, rest) = match text.split_once(" (") {
+        Some((name, rest)) => (name.trim(), rest),
+        None => (text.trim(), ""),
+    };
+    let role = rest
+        .split_once(')')
+        .map(|(role, _)| role.trim().to_string())
+        .filter(|role| !role.is_empty());
+    let cik = text
+        .split_once("CIK:")
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .map(|s| s.to_string());
+
+    HeaderEntity {
+        role,
+        name: Some(name.to_string()).filter(|s| !s.is_empty()),
+        cik,
+    }
+}
+
+fn parse_document_tables(html: &str, lower: &str) -> Result<Vec<IndexDocument>, String> {
+    let mut documents = vec![];
+    let mut offset = 0;
+
+    while let Some(start) = lower[offset..].find("<table class=\"tablefile\"") {
+        let start = offset + start;
+        let end = lower[start..]
+            .find("</table>")
+            .map(|end| start + end)
+            .unwrap_or(lower.len());
+
+        for (index, row) in split_rows(&html[start..end], &lower[start..end])
+            .into_iter()
+            .enumerate()
+        {
+            let cells = split_cells(&row);
+            if cells.len() < 5 {
+                continue;
+            }
+            // Inline XBRL documents are followed by an iXBRL label outside
+            // the link, so prefer the link text.
+            let lower_cell = cells[2].to_ascii_lowercase();
+            let name = find_tag_content(&cells[2], &lower_cell, "a")
+                .map(|content| strip_tags(&content))
+                .unwrap_or_else(|| strip_tags(&cells[2]));
+            if name.is_empty() {
+                continue;
+            }
+
+            documents.push(IndexDocument {
+                sequence: non_empty(strip_tags(&cells[0]))
+                    .map(|s| parse_int64(&s, "Seq"))
+                    .transpose()
+                    .map_err(|e| format!("row {}: {}", index + 1, e))?,
+                description: non_empty(strip_tags(&cells[1])),
+                name,
+                document_type: non_empty(strip_tags(&cells[3])),
+                size: non_empty(strip_tags(&cells[4]))
+                    .map(|s| parse_int64(&s, "Size"))
+                    .transpose()
+                    .map_err(|e| format!("row {}: {}", index + 1, e))?,
+                href: find_href(&cells[2]),
+            });
+        }
+
+        offset = end;
+    }
+
+    Ok(documents)
+}
+
+fn split_rows(html: &str, lower: &str) -> Vec<String> {
+    find_all_tag_contents(html, lower, "tr")
+}
+
+fn split_cells(row: &str) -> Vec<String> {
+    let lower = row.to_ascii_lowercase();
+    find_all_tag_contents(row, &lower, "td")
+}
+
+// Inline XBRL documents link to the viewer, e.g. /ix?doc=/Archives/..., so
+// drop the viewer prefix to get the document itself.
+fn find_href(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let start = lower.find("href=\"")? + "href=\"".len();
+    let end = html[start..].find('"')? + start;
+    let href = &html[start..end];
+    Some(href.strip_prefix("/ix?doc=").unwrap_or(href).to_string())
+}
+
+// Returns the inner html of the element whose opening tag contains `marker`,
+// e.g. `id="formName"`. Nested elements of the same name are not tracked, so
+// this is only used for leaf-ish blocks.
+fn find_element(html: &str, lower: &str, marker: &str) -> Option<String> {
+    find_all_elements(html, lower, marker).into_iter().next()
+}
+
+fn find_all_elements(html: &str, lower: &str, marker: &str) -> Vec<String> {
+    let mut elements = vec![];
+    let mut offset = 0;
+
+    while let Some(found) = lower[offset..].find(marker) {
+        let marker_start = offset + found;
+        let Some(tag_start) = lower[..marker_start].rfind('<') else {
+            break;
+        };
+        let tag_name: String = lower[tag_start + 1..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect();
+        let Some(content_start) = lower[marker_start..]
+            .find('>')
+            .map(|i| marker_start + i + 1)
+        else {
+            break;
+        };
+        let closing = format!("</{}>", tag_name);
+        let content_end = lower[content_start..]
+            .find(&closing)
+            .map(|i| content_start + i)
+            .unwrap_or(lower.len());
+
+        elements.push(html[content_start..content_end].to_string());
+        offset = content_end;
+    }
+
+    elements
+}
+
+fn find_tag_content(html: &str, lower: &str, tag: &str) -> Option<String> {
+    find_all_tag_contents(html, lower, tag).into_iter().next()
+}
+
+fn find_all_tag_contents(html: &str, lower: &str, tag: &str) -> Vec<String> {
+    let mut contents = vec![];
+    let opening = format!("<{}", tag);
+    let closing = format!("</{}>", tag);
+    let mut offset = 0;
+
+    while let Some(found) = lower[offset..].find(&opening) {
+        let tag_start = offset + found;
+        let next = lower[tag_start + opening.len()..].chars().next();
+        if !matches!(
+            next,
+            Some('>') | Some(' ') | Some('\t') | Some('\n') | Some('\r')
+        ) {
+            offset = tag_start + opening.len();
+            continue;
+        }
+        let Some(content_start) = lower[tag_start..].find('>').map(|i| tag_start + i + 1) else {
+            break;
+        };
+        let content_end = lower[content_start..]
+            .find(&closing)
+            .map(|i| content_start + i)
+            .unwrap_or(lower.len());
+
+        contents.push(html[content_start..content_end].to_string());
+        offset = content_end;
+    }
+
+    contents
+}
+
+fn strip_tags(html: &str) -> String {
+    strip_tags_keep_lines(html)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn strip_tags_keep_lines(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode_entities(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&#160;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn non_empty(text: String) -> Option<String> {
+    Some(text).filter(|s| !s.is_empty())
+}
+
+// The SGML header writes dates as YYYYMMDD and the acceptance time as
+// YYYYMMDDHHMMSS; format them the way the -index.htm page does.
+fn format_date(value: &str) -> String {
+    match value.len() {
+        8 if value.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("{}-{}-{}", &value[0..4], &value[4..6], &value[6..8])
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn format_datetime(value: &str) -> String {
+    match value.len() {
+        14 if value.bytes().all(|b| b.is_ascii_digit()) => format!(
+            "{} {}:{}:{}",
+            format_date(&value[0..8]),
+            &value[8..10],
+            &value[10..12],
+            &value[12..14]
+        ),
+        _ => value.to_string(),
+    }
+}
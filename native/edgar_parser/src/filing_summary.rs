@@ -0,0 +1,227 @@
+use crate::xbrl::{Document as XBRLDocument, Fact};
+use crate::xml::{get_bool, get_int32, get_string};
+use roxmltree::Document as XMLDoc;
+use rustler::NifMap;
+use std::collections::{HashMap, HashSet};
+
+// FilingSummary.xml is produced by the EDGAR renderer alongside the R files
+// (R1.htm, R2.htm, ...) of every XBRL filing.
+
+#[derive(NifMap)]
+pub struct FilingSummary {
+    version: Option<String>,
+    report_type: Option<String>,
+    has_presentation_linkbase: Option<bool>,
+    has_calculation_linkbase: Option<bool>,
+    has_inline_xbrl: Option<bool>,
+    reports: Vec<Report>,
+    input_files: Vec<InputFile>,
+    base_taxonomies: Vec<String>,
+}
+
+#[derive(Clone, NifMap)]
+pub struct Report {
+    instance: Option<String>,
+    is_default: Option<bool>,
+    has_embedded_reports: Option<bool>,
+    html_file_name: Option<String>,
+    long_name: Option<String>,
+    short_name: Option<String>,
+    menu_category: Option<String>,
+    position: Option<i32>,
+    role: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct InputFile {
+    name: String,
+    doc_type: Option<String>,
+    original: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct PresentationRole {
+    role: String,
+    concepts: Vec<String>,
+    dimensions: Vec<String>,
+    members: Vec<String>,
+}
+
+#[derive(NifMap)]
+pub struct ReportFacts {
+    report: Report,
+    concepts: Vec<String>,
+    facts: Vec<Fact>,
+}
+
+#[rustler::nif]
+pub fn parse_filing_summary(xml: &str) -> Result<FilingSummary, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+    let version = get_string(&root_node, "Version").ok();
+    let report_type = get_string(&root_node, "ReportType").ok();
+    let has_presentation_linkbase = get_bool(&root_node, "HasPresentationLinkbase").ok();
+    let has_calculation_linkbase = get_bool(&root_node, "HasCalculationLinkbase").ok();
+    let has_inline_xbrl = get_bool(&root_node, "HasInlineXbrl").ok();
+    let reports = parse_reports(&root_node)?;
+    let input_files = parse_input_files(&root_node)?;
+    let base_taxonomies = root_node
+        .children()
+        .filter(|node| node.has_tag_name("BaseTaxonomies"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("BaseTaxonomy"))
+        .filter_map(|node| node.text())
+        .map(|s| s.trim().to_string())
+        .collect();
+
+    Ok(FilingSummary {
+        version,
+        report_type,
+        has_presentation_linkbase,
+        has_calculation_linkbase,
+        has_inline_xbrl,
+        reports,
+        input_files,
+        base_taxonomies,
+    })
+}
+
+fn parse_reports(node: &roxmltree::Node) -> Result<Vec<Report>, String> {
+    let reports = node
+        .children()
+        .filter(|node| node.has_tag_name("MyReports"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("Report"))
+        .map(|report_node| Report {
+            instance: report_node.attribute("instance").map(|s| s.to_string()),
+            is_default: get_bool(&report_node, "IsDefault").ok(),
+            has_embedded_reports: get_bool(&report_node, "HasEmbeddedReports").ok(),
+            html_file_name: get_string(&report_node, "HtmlFileName").ok(),
+            long_name: get_string(&report_node, "LongName").ok(),
+            short_name: get_string(&report_node, "ShortName").ok(),
+            menu_category: get_string(&report_node, "MenuCategory").ok(),
+            position: get_int32(&report_node, "Position").ok(),
+            role: get_string(&report_node, "Role").ok(),
+        })
+        .collect();
+    Ok(reports)
+}
+
+fn parse_input_files(node: &roxmltree::Node) -> Result<Vec<InputFile>, String> {
+    let input_files = node
+        .children()
+        .filter(|node| node.has_tag_name("InputFiles"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("File"))
+        .filter_map(|file_node| {
+            file_node.text().map(|name| InputFile {
+                name: name.trim().to_string(),
+                doc_type: file_node.attribute("doctype").map(|s| s.to_string()),
+                original: file_node.attribute("original").map(|s| s.to_string()),
+            })
+        })
+        .collect();
+    Ok(input_files)
+}
+
+// Collects the concepts presented under each role of a presentation
+// linkbase (`*_pre.xml`), in document order and without duplicates. The
+// Axis and Member (or Domain) locators among them are also listed
+// separately, as they decide which dimensional facts belong to the role.
+#[rustler::nif]
+pub fn parse_presentation_linkbase(xml: &str) -> Result<Vec<PresentationRole>, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+    let xlink_ns = "http://www.w3.org/1999/xlink";
+
+    let roles = root_node
+        .children()
+        .filter(|node| node.has_tag_name("presentationLink"))
+        .filter_map(|link_node| {
+            link_node.attribute((xlink_ns, "role")).map(|role| {
+                let mut seen = HashSet::new();
+                let concepts: Vec<String> = link_node
+                    .children()
+                    .filter(|node| node.has_tag_name("loc"))
+                    .filter_map(|loc_node| loc_node.attribute((xlink_ns, "href")))
+                    .filter_map(concept_from_href)
+                    .filter(|concept| seen.insert(concept.clone()))
+                    .collect();
+                let dimensions = concepts
+                    .iter()
+                    .filter(|concept| concept.ends_with("Axis"))
+                    .cloned()
+                    .collect();
+                let members = concepts
+                    .iter()
+                    .filter(|concept| concept.ends_with("Member") || concept.ends_with("Domain"))
+                    .cloned()
+                    .collect();
+
+                PresentationRole {
+                    role: role.to_string(),
+                    concepts,
+                    dimensions,
+                    members,
+                }
+            })
+        })
+        .collect();
+
+    Ok(roles)
+}
+
+// Locators point at schema elements such as
+// `https://xbrl.fasb.org/us-gaap/2023/elts/us-gaap-2023.xsd#us-gaap_Revenues`.
+// The element id is the prefix and local name joined by an underscore, and
+// parse_xbrl keys facts by local name only.
+fn concept_from_href(href: &str) -> Option<String> {
+    href.rsplit_once('#')
+        .map(|(_, id)| id.split_once('_').map(|(_, name)| name).unwrap_or(id))
+        .map(|name| name.to_string())
+}
+
+// Groups facts from `parse_xbrl` under the FilingSummary report whose role
+// presents their concept. Dimensional facts are only grouped under roles
+// that present each of their axes and members, so segment facts don't show
+// up on the face statements. Reports without a role, such as "All Reports",
+// are skipped.
+#[rustler::nif]
+pub fn group_facts_by_report(
+    summary: FilingSummary,
+    presentation: Vec<PresentationRole>,
+    document: XBRLDocument,
+) -> Vec<ReportFacts> {
+    let roles: HashMap<&str, &PresentationRole> = presentation
+        .iter()
+        .map(|role| (role.role.as_str(), role))
+        .collect();
+
+    summary
+        .reports
+        .into_iter()
+        .filter_map(|report| {
+            let role = report.role.as_deref().and_then(|role| roles.get(role))?;
+            let concepts = role.concepts.to_vec();
+            let concept_set: HashSet<&str> = concepts.iter().map(|c| c.as_str()).collect();
+            let facts = document
+                .facts
+                .iter()
+                .filter(|fact| concept_set.contains(fact.concept.as_str()))
+                .filter(|fact| {
+                    fact.context.segments.iter().all(|segment| {
+                        role.dimensions.contains(&segment.dimension)
+                            && role.members.contains(&segment.member)
+                    })
+                })
+                .cloned()
+                .collect();
+
+            Some(ReportFacts {
+                report,
+                concepts,
+                facts,
+            })
+        })
+        .collect()
+}
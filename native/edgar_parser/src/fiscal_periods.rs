@@ -0,0 +1,398 @@
+use crate::xbrl::{Document, Fact};
+use crate::xml::Value;
+use rustler::NifMap;
+use std::collections::{BTreeMap, HashMap};
+
+// Durations are classified by length in days, with enough slack to cover
+// 52/53-week fiscal calendars (13 or 14 week quarters).
+const QUARTER_DAYS: (i64, i64) = (80, 100);
+const HALF_DAYS: (i64, i64) = (170, 200);
+const NINE_MONTHS_DAYS: (i64, i64) = (260, 290);
+const ANNUAL_DAYS: (i64, i64) = (350, 380);
+
+// A period ending up to a week after the nominal fiscal year end date still
+// belongs to that fiscal year.
+const FISCAL_YEAR_END_SLACK_DAYS: i64 = 7;
+
+const YTD_LABELS: [&str; 4] = ["Q1", "H1", "9M", "FY"];
+
+#[derive(Clone, NifMap)]
+pub struct FiscalFact {
+    fact: Fact,
+    period_type: String,
+    duration_days: Option<i64>,
+    fiscal_year: Option<i32>,
+    fiscal_quarter: Option<i32>,
+    fiscal_period: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct DerivedValue {
+    entity: String,
+    concept: String,
+    unit: Option<String>,
+    fiscal_year: i32,
+    fiscal_period: String,
+    start_date: String,
+    end_date: String,
+    value: f64,
+    method: String,
+}
+
+#[derive(Clone, Copy)]
+struct FiscalYearEnd {
+    month: u32,
+    day: u32,
+}
+
+impl FiscalYearEnd {
+    // Accepts the dei:CurrentFiscalYearEndDate format (--09-28) as well as
+    // MM-DD and the MMDD form used by the submissions api.
+    fn parse(value: &str) -> Result<FiscalYearEnd, String> {
+        let value = value.trim().trim_start_matches("--").replace('-', "");
+        let invalid = || format!("invalid fiscal year end: {}", value);
+
+        if value.len() != 4 || !value.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let month = value[0..2].parse::<u32>().map_err(|_| invalid())?;
+        let day = value[2..4].parse::<u32>().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(invalid());
+        }
+
+        Ok(FiscalYearEnd { month, day })
+    }
+
+    fn date_in(&self, year: i32) -> i64 {
+        let day = self.day.min(days_in_month(year, self.month));
+        days_from_civil(year, self.month, day)
+    }
+
+    // The fiscal year is named after the calendar year in which it ends.
+    fn fiscal_year_of(&self, date: i64) -> i32 {
+        let (year, _, _) = civil_from_days(date);
+        (year - 1..=year + 1)
+            .find(|&year| self.date_in(year) >= date - FISCAL_YEAR_END_SLACK_DAYS)
+            .unwrap_or(year + 1)
+    }
+
+    fn fiscal_quarter_of(&self, date: i64, fiscal_year: i32) -> i32 {
+        let year_start = self.date_in(fiscal_year - 1);
+        let quarter = ((date - year_start) as f64 / 91.3).round() as i32;
+        quarter.clamp(1, 4)
+    }
+}
+
+#[rustler::nif]
+pub fn classify_fiscal_periods(
+    document: Document,
+    fiscal_year_end: Option<String>,
+) -> Result<Vec<FiscalFact>, String> {
+    let period_end_date = dei_text(&document, "DocumentPeriodEndDate");
+    let fiscal_year_end = match fiscal_year_end.or(dei_text(&document, "CurrentFiscalYearEndDate"))
+    {
+        Some(fiscal_year_end) => FiscalYearEnd::parse(&fiscal_year_end)?,
+        // An annual report without the cover page date still tells us the
+        // fiscal year end through its period end date.
+        None => match (
+            dei_text(&document, "DocumentFiscalPeriodFocus").as_deref(),
+            period_end_date.as_deref(),
+        ) {
+            (Some("FY"), Some(period_end_date)) => {
+                FiscalYearEnd::parse(period_end_date.get(5..).unwrap_or_default())?
+            }
+            _ => return Err("CurrentFiscalYearEndDate not found".to_string()),
+        },
+    };
+
+    // Some filers name their fiscal year after the calendar year it starts
+    // in, so line the computed years up with DocumentFiscalYearFocus.
+    let year_offset = match (
+        dei_text(&document, "DocumentFiscalYearFocus").and_then(|s| s.parse::<i32>().ok()),
+        period_end_date.as_deref().and_then(parse_date),
+    ) {
+        (Some(focus_year), Some(period_end)) => {
+            focus_year - fiscal_year_end.fiscal_year_of(period_end)
+        }
+        _ => 0,
+    };
+
+    Ok(document
+        .facts
+        .into_iter()
+        .map(|fact| classify_fact(fact, fiscal_year_end, year_offset))
+        .collect())
+}
+
+#[rustler::nif]
+pub fn derive_fiscal_values(facts: Vec<FiscalFact>) -> Vec<DerivedValue> {
+    let mut groups: BTreeMap<(String, String, Option<String>), Vec<&FiscalFact>> = BTreeMap::new();
+
+    for fact in facts.iter().filter(|fact| {
+        fact.fact.context.segments.is_empty()
+            && fact.fiscal_year.is_some()
+            && fact.fact.numeric_value().is_some()
+    }) {
+        groups
+            .entry((
+                fact.fact.context.entity.clone(),
+                fact.fact.concept.clone(),
+                fact.fact.unit.clone(),
+            ))
+            .or_default()
+            .push(fact);
+    }
+
+    groups
+        .into_iter()
+        .flat_map(|((entity, concept, unit), facts)| {
+            derive_group(&facts)
+                .into_iter()
+                .map(move |derived| DerivedValue {
+                    entity: entity.clone(),
+                    concept: concept.clone(),
+                    unit: unit.clone(),
+                    fiscal_year: derived.fiscal_year,
+                    fiscal_period: derived.fiscal_period,
+                    start_date: format_date(derived.start),
+                    end_date: format_date(derived.end),
+                    value: derived.value,
+                    method: derived.method,
+                })
+        })
+        .collect()
+}
+
+fn classify_fact(fact: Fact, fiscal_year_end: FiscalYearEnd, year_offset: i32) -> FiscalFact {
+    let period = &fact.context.period;
+    let instant = period.instant.as_deref().and_then(parse_date);
+    let start = period.start_date.as_deref().and_then(parse_date);
+    let end = period.end_date.as_deref().and_then(parse_date);
+
+    let (period_type, duration_days, end) = match (instant, start, end) {
+        (Some(instant), _, _) => ("instant", None, Some(instant)),
+        (None, Some(start), Some(end)) => {
+            let days = end - start;
+            (duration_type(days), Some(days), Some(end))
+        }
+        _ => ("other", None, None),
+    };
+
+    let fiscal_year = end.map(|end| fiscal_year_end.fiscal_year_of(end));
+    let fiscal_quarter = end
+        .zip(fiscal_year)
+        .map(|(end, fiscal_year)| fiscal_year_end.fiscal_quarter_of(end, fiscal_year));
+    let fiscal_period = fiscal_quarter.and_then(|quarter| match period_type {
+        "quarter" => Some(format!("Q{}", quarter)),
+        "half" if quarter <= 2 => Some("H1".to_string()),
+        "half" => Some("H2".to_string()),
+        "nine_months" => Some("9M".to_string()),
+        "annual" => Some("FY".to_string()),
+        "instant" if quarter == 4 => Some("FY".to_string()),
+        "instant" => Some(format!("Q{}", quarter)),
+        _ => None,
+    });
+
+    FiscalFact {
+        fact,
+        period_type: period_type.to_string(),
+        duration_days,
+        fiscal_year: fiscal_year.map(|year| year + year_offset),
+        fiscal_quarter,
+        fiscal_period,
+    }
+}
+
+fn duration_type(days: i64) -> &'static str {
+    let within = |(min, max): (i64, i64)| (min..=max).contains(&days);
+
+    if within(QUARTER_DAYS) {
+        "quarter"
+    } else if within(HALF_DAYS) {
+        "half"
+    } else if within(NINE_MONTHS_DAYS) {
+        "nine_months"
+    } else if within(ANNUAL_DAYS) {
+        "annual"
+    } else {
+        "other"
+    }
+}
+
+struct Span {
+    start: i64,
+    end: i64,
+    value: f64,
+}
+
+struct Derived {
+    fiscal_year: i32,
+    fiscal_period: String,
+    start: i64,
+    end: i64,
+    value: f64,
+    method: String,
+}
+
+// Year-to-date facts are keyed by the number of quarters they cover: Q1, H1,
+// 9M and FY. A missing quarter is the difference of consecutive YTD values,
+// and the trailing twelve months is the prior year plus the current YTD less
+// the prior YTD.
+fn derive_group(facts: &[&FiscalFact]) -> Vec<Derived> {
+    let mut ytd: HashMap<(i32, usize), Span> = HashMap::new();
+    let mut quarters: HashMap<(i32, i32), Span> = HashMap::new();
+
+    for fact in facts {
+        let period = &fact.fact.context.period;
+        let (Some(fiscal_year), Some(fiscal_quarter), Some(start), Some(end), Some(value)) = (
+            fact.fiscal_year,
+            fact.fiscal_quarter,
+            period.start_date.as_deref().and_then(parse_date),
+            period.end_date.as_deref().and_then(parse_date),
+            fact.fact.numeric_value(),
+        ) else {
+            continue;
+        };
+        let span = Span { start, end, value };
+
+        let quarters_covered = match fact.period_type.as_str() {
+            "quarter" => {
+                if fiscal_quarter == 1 {
+                    ytd.entry((fiscal_year, 1))
+                        .or_insert(Span { start, end, value });
+                }
+                quarters
+                    .entry((fiscal_year, fiscal_quarter))
+                    .or_insert(span);
+                continue;
+            }
+            "half" => 2,
+            "nine_months" => 3,
+            "annual" => 4,
+            _ => continue,
+        };
+
+        if quarters_covered == fiscal_quarter as usize {
+            ytd.entry((fiscal_year, quarters_covered)).or_insert(span);
+        }
+    }
+
+    let mut fiscal_years: Vec<i32> = ytd.keys().map(|(year, _)| *year).collect();
+    fiscal_years.sort();
+    fiscal_years.dedup();
+
+    let mut derived = vec![];
+
+    for &fiscal_year in &fiscal_years {
+        for covered in 2..=4 {
+            if quarters.contains_key(&(fiscal_year, covered as i32)) {
+                continue;
+            }
+            if let (Some(current), Some(previous)) = (
+                ytd.get(&(fiscal_year, covered)),
+                ytd.get(&(fiscal_year, covered - 1)),
+            ) {
+                if previous.start != current.start {
+                    continue;
+                }
+                derived.push(Derived {
+                    fiscal_year,
+                    fiscal_period: format!("Q{}", covered),
+                    start: previous.end + 1,
+                    end: current.end,
+                    value: current.value - previous.value,
+                    method: format!("{} - {}", YTD_LABELS[covered - 1], YTD_LABELS[covered - 2]),
+                });
+            }
+        }
+
+        for covered in 1..=3 {
+            if let (Some(current), Some(prior_annual), Some(prior)) = (
+                ytd.get(&(fiscal_year, covered)),
+                ytd.get(&(fiscal_year - 1, 4)),
+                ytd.get(&(fiscal_year - 1, covered)),
+            ) {
+                derived.push(Derived {
+                    fiscal_year,
+                    fiscal_period: "TTM".to_string(),
+                    start: prior.end + 1,
+                    end: current.end,
+                    value: prior_annual.value + current.value - prior.value,
+                    method: format!(
+                        "FY(prior) + {} - {}(prior)",
+                        YTD_LABELS[covered - 1],
+                        YTD_LABELS[covered - 1]
+                    ),
+                });
+            }
+        }
+    }
+
+    derived
+}
+
+fn dei_text(document: &Document, concept: &str) -> Option<String> {
+    document
+        .facts
+        .iter()
+        .find(|fact| fact.concept == concept && fact.context.segments.is_empty())
+        .and_then(|fact| match &fact.value {
+            Value::Text(text) => Some(text.trim().to_string()),
+            Value::Int(value) => Some(value.to_string()),
+            _ => None,
+        })
+}
+
+pub(crate) fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.trim().splitn(3, '-');
+    let year = parts.next()?.parse::<i32>().ok()?;
+    let month = parts.next()?.parse::<u32>().ok()?;
+    let day = parts.next()?.get(0..2)?.parse::<u32>().ok()?;
+
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day))
+}
+
+fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01, from http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year } as i64;
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month, day)
+}
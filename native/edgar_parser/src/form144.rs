@@ -0,0 +1,344 @@
+use crate::xml::{get_bool, get_optional_float64, get_string, parse_filer, Filer};
+use roxmltree::Document as XMLDoc;
+use rustler::NifMap;
+
+// https://www.sec.gov/info/edgar/specifications/form144xmltechspec.htm
+
+#[derive(NifMap)]
+pub struct Document {
+    schema_version: Option<String>,
+    submission_type: String,
+    filer: Option<Filer>,
+    issuer: Issuer,
+    seller: Seller,
+    securities_information: Vec<SecuritiesInformation>,
+    securities_to_be_sold: Vec<SecuritiesToBeSold>,
+    nothing_to_report_sold_in_past_3_months: Option<bool>,
+    securities_sold_in_past_3_months: Vec<SecuritiesSoldInPast3Months>,
+    remarks: Option<String>,
+    notice_signature: Option<NoticeSignature>,
+}
+
+#[derive(NifMap)]
+pub struct Issuer {
+    cik: String,
+    name: Option<String>,
+    sec_file_number: Option<String>,
+    address: Option<Address>,
+    contact_phone: Option<String>,
+}
+
+// The person for whose account the securities are to be sold.
+#[derive(NifMap)]
+pub struct Seller {
+    name: Option<String>,
+    relationships_to_issuer: Vec<String>,
+}
+
+#[derive(NifMap)]
+pub struct Address {
+    street1: Option<String>,
+    street2: Option<String>,
+    city: Option<String>,
+    state_or_country: Option<String>,
+    zip_code: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SecuritiesInformation {
+    securities_class_title: Option<String>,
+    broker: Option<Broker>,
+    units_to_be_sold: Option<f64>,
+    aggregate_market_value: Option<f64>,
+    units_outstanding: Option<f64>,
+    approx_sale_date: Option<String>,
+    securities_exchange_name: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct Broker {
+    name: Option<String>,
+    address: Option<Address>,
+}
+
+#[derive(NifMap)]
+pub struct SecuritiesToBeSold {
+    securities_class_title: Option<String>,
+    acquired_date: Option<String>,
+    nature_of_acquisition_transaction: Option<String>,
+    acquired_from: Option<String>,
+    is_gift_transaction: Option<bool>,
+    donor_acquired_date: Option<String>,
+    amount_of_securities_acquired: Option<f64>,
+    payment_date: Option<String>,
+    nature_of_payment: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SecuritiesSoldInPast3Months {
+    seller: Option<Broker>,
+    securities_class_title: Option<String>,
+    sale_date: Option<String>,
+    amount_of_securities_sold: Option<f64>,
+    gross_proceeds: Option<f64>,
+}
+
+#[derive(NifMap)]
+pub struct NoticeSignature {
+    notice_date: Option<String>,
+    plan_adoption_dates: Vec<String>,
+    signature: Option<String>,
+}
+
+#[rustler::nif]
+pub fn parse_form144(xml: &str) -> Result<Document, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+    let schema_version = get_string(&root_node, "schemaVersion").ok();
+
+    let header_data_node = root_node
+        .children()
+        .find(|node| node.has_tag_name("headerData"))
+        .ok_or("headerData not found".to_string())?;
+    let submission_type = get_string(&header_data_node, "submissionType")?;
+    let filer = parse_filer(&header_data_node)?;
+
+    let form_data_node = root_node
+        .children()
+        .find(|node| node.has_tag_name("formData"))
+        .ok_or("formData not found".to_string())?;
+    let issuer = parse_issuer(&form_data_node)?;
+    let seller = parse_seller(&form_data_node)?;
+    let securities_information = parse_securities_information(&form_data_node)?;
+    let securities_to_be_sold = parse_securities_to_be_sold(&form_data_node)?;
+    let nothing_to_report_sold_in_past_3_months = get_bool(
+        &form_data_node,
+        "nothingToReportFlagOnSecuritiesSoldInPast3Months",
+    )
+    .ok();
+    let securities_sold_in_past_3_months = parse_securities_sold_in_past_3_months(&form_data_node)?;
+    let remarks = get_string(&form_data_node, "remarks").ok();
+    let notice_signature = parse_notice_signature(&form_data_node)?;
+
+    Ok(Document {
+        schema_version,
+        submission_type,
+        filer,
+        issuer,
+        seller,
+        securities_information,
+        securities_to_be_sold,
+        nothing_to_report_sold_in_past_3_months,
+        securities_sold_in_past_3_months,
+        remarks,
+        notice_signature,
+    })
+}
+
+fn parse_issuer(node: &roxmltree::Node) -> Result<Issuer, String> {
+    node.children()
+        .find(|node| node.has_tag_name("issuerInfo"))
+        .ok_or("issuerInfo not found".to_string())
+        .and_then(|issuer_node| {
+            let cik = get_string(&issuer_node, "issuerCik")?;
+            let name = get_string(&issuer_node, "issuerName").ok();
+            let sec_file_number = get_string(&issuer_node, "secFileNumber").ok();
+            let address = parse_address(&issuer_node, "issuerAddress")?;
+            let contact_phone = get_string(&issuer_node, "issuerContactPhone").ok();
+
+            Ok(Issuer {
+                cik,
+                name,
+                sec_file_number,
+                address,
+                contact_phone,
+            })
+        })
+}
+
+// The seller is reported within issuerInfo.
+fn parse_seller(node: &roxmltree::Node) -> Result<Seller, String> {
+    node.children()
+        .find(|node| node.has_tag_name("issuerInfo"))
+        .ok_or("issuerInfo not found".to_string())
+        .map(|issuer_node| {
+            let name = get_string(
+                &issuer_node,
+                "nameOfPersonForWhoseAccountTheSecuritiesAreToBeSold",
+            )
+            .ok();
+            let relationships_to_issuer = issuer_node
+                .children()
+                .filter(|node| node.has_tag_name("relationshipsToIssuer"))
+                .flat_map(|node| node.children())
+                .filter(|node| node.has_tag_name("relationshipToIssuer"))
+                .filter_map(|node| node.text())
+                .map(|s| s.trim().to_string())
+                .collect();
+
+            Seller {
+                name,
+                relationships_to_issuer,
+            }
+        })
+}
+
+fn parse_address(node: &roxmltree::Node, tag: &str) -> Result<Option<Address>, String> {
+    node.children()
+        .find(|node| node.has_tag_name(tag))
+        .map(|address_node| {
+            let street1 = get_string(&address_node, "street1").ok();
+            let street2 = get_string(&address_node, "street2").ok();
+            let city = get_string(&address_node, "city").ok();
+            let state_or_country = get_string(&address_node, "stateOrCountry").ok();
+            let zip_code = get_string(&address_node, "zipCode").ok();
+
+            Ok(Address {
+                street1,
+                street2,
+                city,
+                state_or_country,
+                zip_code,
+            })
+        })
+        .transpose()
+}
+
+fn parse_broker(node: &roxmltree::Node, tag: &str) -> Result<Option<Broker>, String> {
+    node.children()
+        .find(|node| node.has_tag_name(tag))
+        .map(|broker_node| {
+            let name = get_string(&broker_node, "name").ok();
+            let address = parse_address(&broker_node, "address")?;
+
+            Ok(Broker { name, address })
+        })
+        .transpose()
+}
+
+fn parse_securities_information(
+    node: &roxmltree::Node,
+) -> Result<Vec<SecuritiesInformation>, String> {
+    node.children()
+        .filter(|node| node.has_tag_name("securitiesInformation"))
+        .enumerate()
+        .map(|(index, info_node)| {
+            parse_securities_information_entry(&info_node)
+                .map_err(|e| format!("securitiesInformation {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+fn parse_securities_information_entry(
+    node: &roxmltree::Node,
+) -> Result<SecuritiesInformation, String> {
+    let securities_class_title = get_string(node, "securitiesClassTitle").ok();
+    let broker = parse_broker(node, "brokerOrMarketmakerDetails")?;
+    let units_to_be_sold = get_optional_float64(node, "noOfUnitsSold")?;
+    let aggregate_market_value = get_optional_float64(node, "aggregateMarketValue")?;
+    let units_outstanding = get_optional_float64(node, "noOfUnitsOutstanding")?;
+    let approx_sale_date = get_string(node, "approxSaleDate").ok();
+    let securities_exchange_name = get_string(node, "securitiesExchangeName").ok();
+
+    Ok(SecuritiesInformation {
+        securities_class_title,
+        broker,
+        units_to_be_sold,
+        aggregate_market_value,
+        units_outstanding,
+        approx_sale_date,
+        securities_exchange_name,
+    })
+}
+
+fn parse_securities_to_be_sold(node: &roxmltree::Node) -> Result<Vec<SecuritiesToBeSold>, String> {
+    node.children()
+        .filter(|node| node.has_tag_name("securitiesToBeSold"))
+        .enumerate()
+        .map(|(index, sold_node)| {
+            parse_securities_to_be_sold_entry(&sold_node)
+                .map_err(|e| format!("securitiesToBeSold {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+fn parse_securities_to_be_sold_entry(node: &roxmltree::Node) -> Result<SecuritiesToBeSold, String> {
+    let securities_class_title = get_string(node, "securitiesClassTitle").ok();
+    let acquired_date = get_string(node, "acquiredDate").ok();
+    let nature_of_acquisition_transaction = get_string(node, "natureOfAcquisitionTransaction").ok();
+    let acquired_from = get_string(node, "nameOfPersonfromWhomAcquired").ok();
+    let is_gift_transaction = get_bool(node, "isGiftTransaction").ok();
+    let donor_acquired_date = get_string(node, "donarAcquiredDate")
+        .or_else(|_| get_string(node, "donorAcquiredDate"))
+        .ok();
+    let amount_of_securities_acquired = get_optional_float64(node, "amountOfSecuritiesAcquired")?;
+    let payment_date = get_string(node, "paymentDate").ok();
+    let nature_of_payment = get_string(node, "natureOfPayment").ok();
+
+    Ok(SecuritiesToBeSold {
+        securities_class_title,
+        acquired_date,
+        nature_of_acquisition_transaction,
+        acquired_from,
+        is_gift_transaction,
+        donor_acquired_date,
+        amount_of_securities_acquired,
+        payment_date,
+        nature_of_payment,
+    })
+}
+
+fn parse_securities_sold_in_past_3_months(
+    node: &roxmltree::Node,
+) -> Result<Vec<SecuritiesSoldInPast3Months>, String> {
+    node.children()
+        .filter(|node| node.has_tag_name("securitiesSoldInPast3Months"))
+        .enumerate()
+        .map(|(index, sold_node)| {
+            parse_securities_sold_in_past_3_months_entry(&sold_node)
+                .map_err(|e| format!("securitiesSoldInPast3Months {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+fn parse_securities_sold_in_past_3_months_entry(
+    node: &roxmltree::Node,
+) -> Result<SecuritiesSoldInPast3Months, String> {
+    let seller = parse_broker(node, "sellerDetails")?;
+    let securities_class_title = get_string(node, "securitiesClassTitle").ok();
+    let sale_date = get_string(node, "saleDate").ok();
+    let amount_of_securities_sold = get_optional_float64(node, "amountOfSecuritiesSold")?;
+    let gross_proceeds = get_optional_float64(node, "grossProceeds")?;
+
+    Ok(SecuritiesSoldInPast3Months {
+        seller,
+        securities_class_title,
+        sale_date,
+        amount_of_securities_sold,
+        gross_proceeds,
+    })
+}
+
+fn parse_notice_signature(node: &roxmltree::Node) -> Result<Option<NoticeSignature>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("noticeSignature"))
+        .map(|signature_node| {
+            let notice_date = get_string(&signature_node, "noticeDate").ok();
+            let plan_adoption_dates = signature_node
+                .children()
+                .filter(|node| node.has_tag_name("planAdoptionDates"))
+                .flat_map(|node| node.children())
+                .filter(|node| node.has_tag_name("planAdoptionDate"))
+                .filter_map(|node| node.text())
+                .map(|s| s.trim().to_string())
+                .collect();
+            let signature = get_string(&signature_node, "signature").ok();
+
+            Ok(NoticeSignature {
+                notice_date,
+                plan_adoption_dates,
+                signature,
+            })
+        })
+        .transpose()
+}
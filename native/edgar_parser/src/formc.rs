@@ -0,0 +1,426 @@
+use crate::xml::{get_bool, get_optional_float64, get_optional_int64, get_string};
+use roxmltree::Document as XMLDoc;
+use rustler::NifMap;
+
+// https://www.sec.gov/info/edgar/specifications/form-c-xml-tech-specs
+
+#[derive(NifMap)]
+pub struct Document {
+    schema_version: Option<String>,
+    header_data: HeaderData,
+    form_data: FormData,
+}
+
+#[derive(NifMap)]
+pub struct HeaderData {
+    submission_type: String,
+    filer_info: FilerInfo,
+}
+
+#[derive(NifMap)]
+pub struct FilerInfo {
+    live_test_flag: Option<String>,
+    credentials: Credentials,
+    period: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct Credentials {
+    cik: String,
+    ccc: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct FormData {
+    issuer_information: IssuerInformation,
+    offering_information: Option<OfferingInformation>,
+    annual_report_disclosure: Option<AnnualReportDisclosure>,
+    signature_info: Option<SignatureInfo>,
+}
+
+#[derive(NifMap)]
+pub struct IssuerInformation {
+    issuer: Issuer,
+    is_co_issuer: Option<bool>,
+    intermediary: Option<Intermediary>,
+    is_amendment: Option<bool>,
+    progress_update: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct Issuer {
+    name: String,
+    legal_status: Option<String>,
+    legal_status_other_desc: Option<String>,
+    jurisdiction_of_organization: Option<String>,
+    date_of_incorporation: Option<String>,
+    address: Option<Address>,
+    website: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct Address {
+    street1: Option<String>,
+    street2: Option<String>,
+    city: Option<String>,
+    state_or_country: Option<String>,
+    zip_code: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct Intermediary {
+    name: Option<String>,
+    cik: Option<String>,
+    file_number: Option<String>,
+    crd_number: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct OfferingInformation {
+    compensation_amount: Option<String>,
+    financial_interest: Option<String>,
+    security_offered_type: Option<String>,
+    security_offered_other_desc: Option<String>,
+    number_of_securities_offered: Option<f64>,
+    price: Option<f64>,
+    price_determination_method: Option<String>,
+    target_offering_amount: Option<f64>,
+    over_subscription_accepted: Option<bool>,
+    over_subscription_allocation_type: Option<String>,
+    maximum_offering_amount: Option<f64>,
+    deadline_date: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct AnnualReportDisclosure {
+    current_employees: Option<i64>,
+    most_recent_fiscal_year: FinancialCondition,
+    prior_fiscal_year: FinancialCondition,
+    offering_jurisdictions: Vec<String>,
+}
+
+#[derive(NifMap)]
+pub struct FinancialCondition {
+    total_assets: Option<f64>,
+    cash_and_equivalents: Option<f64>,
+    accounts_receivable: Option<f64>,
+    short_term_debt: Option<f64>,
+    long_term_debt: Option<f64>,
+    revenue: Option<f64>,
+    cost_of_goods_sold: Option<f64>,
+    taxes_paid: Option<f64>,
+    net_income: Option<f64>,
+}
+
+#[derive(NifMap)]
+pub struct SignatureInfo {
+    issuer_signature: Option<IssuerSignature>,
+    signature_persons: Vec<SignaturePerson>,
+}
+
+#[derive(NifMap)]
+pub struct IssuerSignature {
+    issuer: Option<String>,
+    signature: Option<String>,
+    title: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SignaturePerson {
+    signature: Option<String>,
+    title: Option<String>,
+    date: Option<String>,
+}
+
+#[rustler::nif]
+pub fn parse_formc(xml: &str) -> Result<Document, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+    let schema_version = get_string(&root_node, "schemaVersion").ok();
+    let header_data = parse_header_data(&root_node)?;
+    let form_data = parse_form_data(&root_node)?;
+
+    Ok(Document {
+        schema_version,
+        header_data,
+        form_data,
+    })
+}
+
+fn parse_header_data(node: &roxmltree::Node) -> Result<HeaderData, String> {
+    node.children()
+        .find(|node| node.has_tag_name("headerData"))
+        .ok_or("headerData not found".to_string())
+        .and_then(|header_data_node| {
+            let submission_type = get_string(&header_data_node, "submissionType")?;
+            let filer_info = parse_filer_info(&header_data_node)?;
+
+            Ok(HeaderData {
+                submission_type,
+                filer_info,
+            })
+        })
+}
+
+fn parse_filer_info(node: &roxmltree::Node) -> Result<FilerInfo, String> {
+    node.children()
+        .find(|node| node.has_tag_name("filerInfo"))
+        .ok_or("filerInfo not found".to_string())
+        .and_then(|filer_info_node| {
+            let live_test_flag = get_string(&filer_info_node, "liveTestFlag").ok();
+            let credentials = parse_credentials(&filer_info_node)?;
+            let period = get_string(&filer_info_node, "period").ok();
+
+            Ok(FilerInfo {
+                live_test_flag,
+                credentials,
+                period,
+            })
+        })
+}
+
+fn parse_credentials(node: &roxmltree::Node) -> Result<Credentials, String> {
+    node.descendants()
+        .find(|node| node.has_tag_name("filerCredentials"))
+        .ok_or("filerCredentials not found".to_string())
+        .and_then(|credentials_node| {
+            let cik = get_string(&credentials_node, "filerCik")?;
+            let ccc = get_string(&credentials_node, "filerCcc").ok();
+
+            Ok(Credentials { cik, ccc })
+        })
+}
+
+fn parse_form_data(node: &roxmltree::Node) -> Result<FormData, String> {
+    node.children()
+        .find(|node| node.has_tag_name("formData"))
+        .ok_or("formData not found".to_string())
+        .and_then(|form_data_node| {
+            let issuer_information = parse_issuer_information(&form_data_node)?;
+            let offering_information = parse_offering_information(&form_data_node)?;
+            let annual_report_disclosure = parse_annual_report_disclosure(&form_data_node)?;
+            let signature_info = parse_signature_info(&form_data_node)?;
+
+            Ok(FormData {
+                issuer_information,
+                offering_information,
+                annual_report_disclosure,
+                signature_info,
+            })
+        })
+}
+
+fn parse_issuer_information(node: &roxmltree::Node) -> Result<IssuerInformation, String> {
+    node.children()
+        .find(|node| node.has_tag_name("issuerInformation"))
+        .ok_or("issuerInformation not found".to_string())
+        .and_then(|issuer_information_node| {
+            let issuer = parse_issuer(&issuer_information_node)?;
+            let is_co_issuer = get_bool(&issuer_information_node, "isCoIssuer").ok();
+            let intermediary = parse_intermediary(&issuer_information_node);
+            let is_amendment = get_bool(&issuer_information_node, "isAmendment").ok();
+            let progress_update = get_string(&issuer_information_node, "progressUpdate").ok();
+
+            Ok(IssuerInformation {
+                issuer,
+                is_co_issuer,
+                intermediary,
+                is_amendment,
+                progress_update,
+            })
+        })
+}
+
+fn parse_issuer(node: &roxmltree::Node) -> Result<Issuer, String> {
+    node.children()
+        .find(|node| node.has_tag_name("issuerInfo"))
+        .ok_or("issuerInfo not found".to_string())
+        .and_then(|issuer_node| {
+            let name = get_string(&issuer_node, "nameOfIssuer")?;
+            let legal_status_node = issuer_node
+                .children()
+                .find(|node| node.has_tag_name("legalStatus"));
+            let legal_status =
+                legal_status_node.and_then(|node| get_string(&node, "legalStatusForm").ok());
+            let legal_status_other_desc =
+                legal_status_node.and_then(|node| get_string(&node, "legalStatusOtherDesc").ok());
+            let jurisdiction_of_organization = legal_status_node
+                .and_then(|node| get_string(&node, "jurisdictionOrganization").ok());
+            let date_of_incorporation =
+                legal_status_node.and_then(|node| get_string(&node, "dateIncorporation").ok());
+            let address = parse_address(&issuer_node)?;
+            let website = get_string(&issuer_node, "issuerWebsite").ok();
+
+            Ok(Issuer {
+                name,
+                legal_status,
+                legal_status_other_desc,
+                jurisdiction_of_organization,
+                date_of_incorporation,
+                address,
+                website,
+            })
+        })
+}
+
+fn parse_address(node: &roxmltree::Node) -> Result<Option<Address>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("issuerAddress"))
+        .map(|address_node| {
+            let street1 = get_string(&address_node, "street1").ok();
+            let street2 = get_string(&address_node, "street2").ok();
+            let city = get_string(&address_node, "city").ok();
+            let state_or_country = get_string(&address_node, "stateOrCountry").ok();
+            let zip_code = get_string(&address_node, "zipCode").ok();
+
+            Ok(Address {
+                street1,
+                street2,
+                city,
+                state_or_country,
+                zip_code,
+            })
+        })
+        .transpose()
+}
+
+fn parse_intermediary(node: &roxmltree::Node) -> Option<Intermediary> {
+    let name = get_string(node, "companyName").ok();
+    let cik = get_string(node, "commissionCik").ok();
+    let file_number = get_string(node, "commissionFileNumber").ok();
+    let crd_number = get_string(node, "crdNumber").ok();
+
+    if name.is_none() && cik.is_none() && file_number.is_none() && crd_number.is_none() {
+        return None;
+    }
+
+    Some(Intermediary {
+        name,
+        cik,
+        file_number,
+        crd_number,
+    })
+}
+
+fn parse_offering_information(
+    node: &roxmltree::Node,
+) -> Result<Option<OfferingInformation>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("offeringInformation"))
+        .map(|offering_node| {
+            let compensation_amount = get_string(&offering_node, "compensationAmount").ok();
+            let financial_interest = get_string(&offering_node, "financialInterest").ok();
+            let security_offered_type = get_string(&offering_node, "securityOfferedType").ok();
+            let security_offered_other_desc =
+                get_string(&offering_node, "securityOfferedOtherDesc").ok();
+            let number_of_securities_offered =
+                get_optional_float64(&offering_node, "noOfSecurityOffered")?;
+            let price = get_optional_float64(&offering_node, "price")?;
+            let price_determination_method =
+                get_string(&offering_node, "priceDeterminationMethod").ok();
+            let target_offering_amount = get_optional_float64(&offering_node, "offeringAmount")?;
+            let over_subscription_accepted =
+                get_bool(&offering_node, "overSubscriptionAccepted").ok();
+            let over_subscription_allocation_type =
+                get_string(&offering_node, "overSubscriptionAllocationType").ok();
+            let maximum_offering_amount =
+                get_optional_float64(&offering_node, "maximumOfferingAmount")?;
+            let deadline_date = get_string(&offering_node, "deadlineDate").ok();
+
+            Ok(OfferingInformation {
+                compensation_amount,
+                financial_interest,
+                security_offered_type,
+                security_offered_other_desc,
+                number_of_securities_offered,
+                price,
+                price_determination_method,
+                target_offering_amount,
+                over_subscription_accepted,
+                over_subscription_allocation_type,
+                maximum_offering_amount,
+                deadline_date,
+            })
+        })
+        .transpose()
+}
+
+fn parse_annual_report_disclosure(
+    node: &roxmltree::Node,
+) -> Result<Option<AnnualReportDisclosure>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("annualReportDisclosureRequirements"))
+        .map(|disclosure_node| {
+            let current_employees = get_optional_int64(&disclosure_node, "currentEmployees")?;
+            let most_recent_fiscal_year =
+                parse_financial_condition(&disclosure_node, "MostRecentFiscalYear")?;
+            let prior_fiscal_year = parse_financial_condition(&disclosure_node, "PriorFiscalYear")?;
+            let offering_jurisdictions = disclosure_node
+                .children()
+                .filter(|node| node.has_tag_name("issueJurisdictionSecuritiesOffering"))
+                .filter_map(|node| node.text())
+                .map(|s| s.trim().to_string())
+                .collect();
+
+            Ok(AnnualReportDisclosure {
+                current_employees,
+                most_recent_fiscal_year,
+                prior_fiscal_year,
+                offering_jurisdictions,
+            })
+        })
+        .transpose()
+}
+
+// Each figure is reported twice, suffixed with MostRecentFiscalYear and
+// PriorFiscalYear, e.g. totalAssetMostRecentFiscalYear.
+fn parse_financial_condition(
+    node: &roxmltree::Node,
+    suffix: &str,
+) -> Result<FinancialCondition, String> {
+    let get = |prefix: &str| get_optional_float64(node, &format!("{}{}", prefix, suffix));
+
+    Ok(FinancialCondition {
+        total_assets: get("totalAsset")?,
+        cash_and_equivalents: get("cashEqui")?,
+        accounts_receivable: get("actReceived")?,
+        short_term_debt: get("shortTermDebt")?,
+        long_term_debt: get("longTermDebt")?,
+        revenue: get("revenue")?,
+        cost_of_goods_sold: get("costGoodsSold")?,
+        taxes_paid: get("taxPaid")?,
+        net_income: get("netIncome")?,
+    })
+}
+
+fn parse_signature_info(node: &roxmltree::Node) -> Result<Option<SignatureInfo>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("signatureInfo"))
+        .map(|signature_info_node| {
+            let issuer_signature = signature_info_node
+                .children()
+                .find(|node| node.has_tag_name("issuerSignature"))
+                .map(|issuer_signature_node| IssuerSignature {
+                    issuer: get_string(&issuer_signature_node, "issuer").ok(),
+                    signature: get_string(&issuer_signature_node, "issuerSignature").ok(),
+                    title: get_string(&issuer_signature_node, "issuerTitle").ok(),
+                });
+            let signature_persons = signature_info_node
+                .children()
+                .filter(|node| node.has_tag_name("signaturePersons"))
+                .flat_map(|node| node.children())
+                .filter(|node| node.has_tag_name("signaturePerson"))
+                .map(|person_node| SignaturePerson {
+                    signature: get_string(&person_node, "personSignature").ok(),
+                    title: get_string(&person_node, "personTitle").ok(),
+                    date: get_string(&person_node, "signatureDate").ok(),
+                })
+                .collect();
+
+            Ok(SignatureInfo {
+                issuer_signature,
+                signature_persons,
+            })
+        })
+        .transpose()
+}
@@ -0,0 +1,641 @@
+use crate::xml::{get_bool, get_int64, get_optional_int64, get_string};
+use roxmltree::Document as XMLDoc;
+use rustler::NifMap;
+
+// https://www.sec.gov/info/edgar/specifications/formdxmltechspec.htm
+
+#[derive(NifMap)]
+pub struct Document {
+    schema_version: Option<String>,
+    submission_type: String,
+    test_or_live: Option<String>,
+    primary_issuer: Issuer,
+    issuers: Vec<Issuer>,
+    related_persons: Vec<RelatedPerson>,
+    offering_data: OfferingData,
+}
+
+#[derive(NifMap)]
+pub struct Issuer {
+    cik: String,
+    entity_name: String,
+    address: Option<Address>,
+    phone_number: Option<String>,
+    jurisdiction_of_inc: Option<String>,
+    previous_names: Vec<String>,
+    edgar_previous_names: Vec<String>,
+    entity_type: Option<String>,
+    entity_type_other_desc: Option<String>,
+    year_of_inc: Option<YearOfInc>,
+}
+
+#[derive(NifMap)]
+pub struct Address {
+    street1: Option<String>,
+    street2: Option<String>,
+    city: Option<String>,
+    state_or_country: Option<String>,
+    state_or_country_description: Option<String>,
+    zip_code: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct YearOfInc {
+    over_five_years: Option<bool>,
+    within_five_years: Option<bool>,
+    yet_to_be_formed: Option<bool>,
+    value: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct RelatedPerson {
+    first_name: Option<String>,
+    middle_name: Option<String>,
+    last_name: Option<String>,
+    address: Option<Address>,
+    relationships: Vec<String>,
+    relationship_clarification: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct OfferingData {
+    industry_group: Option<IndustryGroup>,
+    issuer_size: Option<IssuerSize>,
+    federal_exemptions_exclusions: Vec<String>,
+    type_of_filing: Option<TypeOfFiling>,
+    duration_of_offering_more_than_one_year: Option<bool>,
+    types_of_securities_offered: Option<TypesOfSecuritiesOffered>,
+    is_business_combination_transaction: Option<bool>,
+    business_combination_clarification: Option<String>,
+    minimum_investment_accepted: Option<i64>,
+    sales_compensation_recipients: Vec<SalesCompensationRecipient>,
+    offering_sales_amounts: Option<OfferingSalesAmounts>,
+    investors: Option<Investors>,
+    sales_commissions_finders_fees: Option<SalesCommissionsFindersFees>,
+    use_of_proceeds: Option<UseOfProceeds>,
+    signature_block: Option<SignatureBlock>,
+}
+
+#[derive(NifMap)]
+pub struct IndustryGroup {
+    industry_group_type: String,
+    investment_fund_type: Option<String>,
+    is_40_act: Option<bool>,
+}
+
+#[derive(NifMap)]
+pub struct IssuerSize {
+    revenue_range: Option<String>,
+    aggregate_net_asset_value_range: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct TypeOfFiling {
+    is_amendment: Option<bool>,
+    previous_accession_number: Option<String>,
+    date_of_first_sale: Option<String>,
+    first_sale_yet_to_occur: Option<bool>,
+}
+
+#[derive(NifMap)]
+pub struct TypesOfSecuritiesOffered {
+    is_equity_type: Option<bool>,
+    is_debt_type: Option<bool>,
+    is_option_to_acquire_type: Option<bool>,
+    is_security_to_be_acquired_type: Option<bool>,
+    is_pooled_investment_fund_type: Option<bool>,
+    is_tenant_in_common_type: Option<bool>,
+    is_mineral_property_type: Option<bool>,
+    is_other_type: Option<bool>,
+    description_of_other_type: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SalesCompensationRecipient {
+    name: Option<String>,
+    crd_number: Option<String>,
+    associated_broker_dealer_name: Option<String>,
+    associated_broker_dealer_crd_number: Option<String>,
+    address: Option<Address>,
+    states_of_solicitation: Vec<String>,
+    foreign_solicitation: Option<bool>,
+}
+
+#[derive(NifMap)]
+pub struct OfferingSalesAmounts {
+    total_offering_amount: Option<i64>,
+    is_total_offering_amount_indefinite: bool,
+    total_amount_sold: Option<i64>,
+    total_remaining: Option<i64>,
+    is_total_remaining_indefinite: bool,
+    clarification_of_response: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct Investors {
+    has_non_accredited_investors: Option<bool>,
+    number_non_accredited_investors: Option<i64>,
+    total_number_already_invested: Option<i64>,
+}
+
+#[derive(NifMap)]
+pub struct SalesCommissionsFindersFees {
+    sales_commissions: Option<i64>,
+    is_sales_commissions_estimate: Option<bool>,
+    finders_fees: Option<i64>,
+    is_finders_fees_estimate: Option<bool>,
+    clarification_of_response: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct UseOfProceeds {
+    gross_proceeds_used: Option<i64>,
+    is_estimate: Option<bool>,
+    clarification_of_response: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SignatureBlock {
+    authorized_representative: Option<bool>,
+    signatures: Vec<Signature>,
+}
+
+#[derive(NifMap)]
+pub struct Signature {
+    issuer_name: Option<String>,
+    signature_name: Option<String>,
+    name_of_signer: Option<String>,
+    signature_title: Option<String>,
+    signature_date: Option<String>,
+}
+
+#[rustler::nif]
+pub fn parse_formd(xml: &str) -> Result<Document, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+    let schema_version = get_string(&root_node, "schemaVersion").ok();
+    let submission_type = get_string(&root_node, "submissionType")?;
+    let test_or_live = get_string(&root_node, "testOrLive").ok();
+    let primary_issuer = root_node
+        .children()
+        .find(|node| node.has_tag_name("primaryIssuer"))
+        .ok_or("primaryIssuer not found".to_string())
+        .and_then(|issuer_node| parse_issuer(&issuer_node))?;
+    let issuers = parse_issuers(&root_node)?;
+    let related_persons = parse_related_persons(&root_node)?;
+    let offering_data = parse_offering_data(&root_node)?;
+
+    Ok(Document {
+        schema_version,
+        submission_type,
+        test_or_live,
+        primary_issuer,
+        issuers,
+        related_persons,
+        offering_data,
+    })
+}
+
+fn parse_issuers(node: &roxmltree::Node) -> Result<Vec<Issuer>, String> {
+    node.children()
+        .filter(|node| node.has_tag_name("issuerList"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("issuer"))
+        .map(|issuer_node| parse_issuer(&issuer_node))
+        .collect()
+}
+
+fn parse_issuer(node: &roxmltree::Node) -> Result<Issuer, String> {
+    let cik = get_string(node, "cik")?;
+    let entity_name = get_string(node, "entityName")?;
+    let address = parse_address(node, "issuerAddress")?;
+    let phone_number = get_string(node, "issuerPhoneNumber").ok();
+    let jurisdiction_of_inc = get_string(node, "jurisdictionOfInc").ok();
+    let previous_names = get_list(node, "issuerPreviousNameList");
+    let edgar_previous_names = get_list(node, "edgarPreviousNameList");
+    let entity_type = get_string(node, "entityType").ok();
+    let entity_type_other_desc = get_string(node, "entityTypeOtherDesc").ok();
+    let year_of_inc = parse_year_of_inc(node)?;
+
+    Ok(Issuer {
+        cik,
+        entity_name,
+        address,
+        phone_number,
+        jurisdiction_of_inc,
+        previous_names,
+        edgar_previous_names,
+        entity_type,
+        entity_type_other_desc,
+        year_of_inc,
+    })
+}
+
+fn parse_address(node: &roxmltree::Node, tag: &str) -> Result<Option<Address>, String> {
+    node.children()
+        .find(|node| node.has_tag_name(tag))
+        .map(|address_node| {
+            let street1 = get_string(&address_node, "street1").ok();
+            let street2 = get_string(&address_node, "street2").ok();
+            let city = get_string(&address_node, "city").ok();
+            let state_or_country = get_string(&address_node, "stateOrCountry").ok();
+            let state_or_country_description =
+                get_string(&address_node, "stateOrCountryDescription").ok();
+            let zip_code = get_string(&address_node, "zipCode").ok();
+
+            Ok(Address {
+                street1,
+                street2,
+                city,
+                state_or_country,
+                state_or_country_description,
+                zip_code,
+            })
+        })
+        .transpose()
+}
+
+fn parse_year_of_inc(node: &roxmltree::Node) -> Result<Option<YearOfInc>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("yearOfInc"))
+        .map(|year_node| {
+            let over_five_years = get_bool(&year_node, "overFiveYears").ok();
+            let within_five_years = get_bool(&year_node, "withinFiveYears").ok();
+            let yet_to_be_formed = get_bool(&year_node, "yetToBeFormed").ok();
+            let value = get_string(&year_node, "value").ok();
+
+            Ok(YearOfInc {
+                over_five_years,
+                within_five_years,
+                yet_to_be_formed,
+                value,
+            })
+        })
+        .transpose()
+}
+
+fn parse_related_persons(node: &roxmltree::Node) -> Result<Vec<RelatedPerson>, String> {
+    node.children()
+        .filter(|node| node.has_tag_name("relatedPersonsList"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("relatedPersonInfo"))
+        .map(|person_node| {
+            let name_node = person_node
+                .children()
+                .find(|node| node.has_tag_name("relatedPersonName"));
+            let first_name = name_node.and_then(|node| get_string(&node, "firstName").ok());
+            let middle_name = name_node.and_then(|node| get_string(&node, "middleName").ok());
+            let last_name = name_node.and_then(|node| get_string(&node, "lastName").ok());
+            let address = parse_address(&person_node, "relatedPersonAddress")?;
+            let relationships = get_list(&person_node, "relatedPersonRelationshipList");
+            let relationship_clarification =
+                get_string(&person_node, "relationshipClarification").ok();
+
+            Ok(RelatedPerson {
+                first_name,
+                middle_name,
+                last_name,
+                address,
+                relationships,
+                relationship_clarification,
+            })
+        })
+        .collect()
+}
+
+fn parse_offering_data(node: &roxmltree::Node) -> Result<OfferingData, String> {
+    node.children()
+        .find(|node| node.has_tag_name("offeringData"))
+        .ok_or("offeringData not found".to_string())
+        .and_then(|offering_node| {
+            let industry_group = parse_industry_group(&offering_node)?;
+            let issuer_size = parse_issuer_size(&offering_node)?;
+            let federal_exemptions_exclusions =
+                get_list(&offering_node, "federalExemptionsExclusions");
+            let type_of_filing = parse_type_of_filing(&offering_node)?;
+            let duration_of_offering_more_than_one_year = offering_node
+                .children()
+                .find(|node| node.has_tag_name("durationOfOffering"))
+                .and_then(|node| get_bool(&node, "moreThanOneYear").ok());
+            let types_of_securities_offered = parse_types_of_securities_offered(&offering_node)?;
+            let business_combination_node = offering_node
+                .children()
+                .find(|node| node.has_tag_name("businessCombinationTransaction"));
+            let is_business_combination_transaction = business_combination_node
+                .and_then(|node| get_bool(&node, "isBusinessCombinationTransaction").ok());
+            let business_combination_clarification = business_combination_node
+                .and_then(|node| get_string(&node, "clarificationOfResponse").ok());
+            let minimum_investment_accepted =
+                get_optional_int64(&offering_node, "minimumInvestmentAccepted")?;
+            let sales_compensation_recipients =
+                parse_sales_compensation_recipients(&offering_node)?;
+            let offering_sales_amounts = parse_offering_sales_amounts(&offering_node)?;
+            let investors = parse_investors(&offering_node)?;
+            let sales_commissions_finders_fees =
+                parse_sales_commissions_finders_fees(&offering_node)?;
+            let use_of_proceeds = parse_use_of_proceeds(&offering_node)?;
+            let signature_block = parse_signature_block(&offering_node)?;
+
+            Ok(OfferingData {
+                industry_group,
+                issuer_size,
+                federal_exemptions_exclusions,
+                type_of_filing,
+                duration_of_offering_more_than_one_year,
+                types_of_securities_offered,
+                is_business_combination_transaction,
+                business_combination_clarification,
+                minimum_investment_accepted,
+                sales_compensation_recipients,
+                offering_sales_amounts,
+                investors,
+                sales_commissions_finders_fees,
+                use_of_proceeds,
+                signature_block,
+            })
+        })
+}
+
+fn parse_industry_group(node: &roxmltree::Node) -> Result<Option<IndustryGroup>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("industryGroup"))
+        .map(|industry_node| {
+            let industry_group_type = get_string(&industry_node, "industryGroupType")?;
+            let fund_info_node = industry_node
+                .children()
+                .find(|node| node.has_tag_name("investmentFundInfo"));
+            let investment_fund_type =
+                fund_info_node.and_then(|node| get_string(&node, "investmentFundType").ok());
+            let is_40_act = fund_info_node.and_then(|node| get_bool(&node, "is40Act").ok());
+
+            Ok(IndustryGroup {
+                industry_group_type,
+                investment_fund_type,
+                is_40_act,
+            })
+        })
+        .transpose()
+}
+
+fn parse_issuer_size(node: &roxmltree::Node) -> Result<Option<IssuerSize>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("issuerSize"))
+        .map(|size_node| {
+            let revenue_range = get_string(&size_node, "revenueRange").ok();
+            let aggregate_net_asset_value_range =
+                get_string(&size_node, "aggregateNetAssetValueRange").ok();
+
+            Ok(IssuerSize {
+                revenue_range,
+                aggregate_net_asset_value_range,
+            })
+        })
+        .transpose()
+}
+
+fn parse_type_of_filing(node: &roxmltree::Node) -> Result<Option<TypeOfFiling>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("typeOfFiling"))
+        .map(|filing_node| {
+            let amendment_node = filing_node
+                .children()
+                .find(|node| node.has_tag_name("newOrAmendment"));
+            let is_amendment = amendment_node.and_then(|node| get_bool(&node, "isAmendment").ok());
+            let previous_accession_number =
+                amendment_node.and_then(|node| get_string(&node, "previousAccessionNumber").ok());
+            let first_sale_node = filing_node
+                .children()
+                .find(|node| node.has_tag_name("dateOfFirstSale"));
+            let date_of_first_sale =
+                first_sale_node.and_then(|node| get_string(&node, "value").ok());
+            let first_sale_yet_to_occur =
+                first_sale_node.and_then(|node| get_bool(&node, "yetToOccur").ok());
+
+            Ok(TypeOfFiling {
+                is_amendment,
+                previous_accession_number,
+                date_of_first_sale,
+                first_sale_yet_to_occur,
+            })
+        })
+        .transpose()
+}
+
+fn parse_types_of_securities_offered(
+    node: &roxmltree::Node,
+) -> Result<Option<TypesOfSecuritiesOffered>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("typesOfSecuritiesOffered"))
+        .map(|types_node| {
+            let is_equity_type = get_bool(&types_node, "isEquityType").ok();
+            let is_debt_type = get_bool(&types_node, "isDebtType").ok();
+            let is_option_to_acquire_type = get_bool(&types_node, "isOptionToAcquireType").ok();
+            let is_security_to_be_acquired_type =
+                get_bool(&types_node, "isSecurityToBeAcquiredType").ok();
+            let is_pooled_investment_fund_type =
+                get_bool(&types_node, "isPooledInvestmentFundType").ok();
+            let is_tenant_in_common_type = get_bool(&types_node, "isTenantInCommonType").ok();
+            let is_mineral_property_type = get_bool(&types_node, "isMineralPropertyType").ok();
+            let is_other_type = get_bool(&types_node, "isOtherType").ok();
+            let description_of_other_type = get_string(&types_node, "descriptionOfOtherType").ok();
+
+            Ok(TypesOfSecuritiesOffered {
+                is_equity_type,
+                is_debt_type,
+                is_option_to_acquire_type,
+                is_security_to_be_acquired_type,
+                is_pooled_investment_fund_type,
+                is_tenant_in_common_type,
+                is_mineral_property_type,
+                is_other_type,
+                description_of_other_type,
+            })
+        })
+        .transpose()
+}
+
+fn parse_sales_compensation_recipients(
+    node: &roxmltree::Node,
+) -> Result<Vec<SalesCompensationRecipient>, String> {
+    node.children()
+        .filter(|node| node.has_tag_name("salesCompensationList"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("recipient"))
+        .map(|recipient_node| {
+            let name = get_string(&recipient_node, "recipientName").ok();
+            let crd_number = get_string(&recipient_node, "recipientCRDNumber").ok();
+            let associated_broker_dealer_name =
+                get_string(&recipient_node, "associatedBDName").ok();
+            let associated_broker_dealer_crd_number =
+                get_string(&recipient_node, "associatedBDCRDNumber").ok();
+            let address = parse_address(&recipient_node, "recipientAddress")?;
+            let states_of_solicitation = get_list(&recipient_node, "statesOfSolicitationList");
+            let foreign_solicitation = get_bool(&recipient_node, "foreignSolicitation").ok();
+
+            Ok(SalesCompensationRecipient {
+                name,
+                crd_number,
+                associated_broker_dealer_name,
+                associated_broker_dealer_crd_number,
+                address,
+                states_of_solicitation,
+                foreign_solicitation,
+            })
+        })
+        .collect()
+}
+
+fn parse_offering_sales_amounts(
+    node: &roxmltree::Node,
+) -> Result<Option<OfferingSalesAmounts>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("offeringSalesAmounts"))
+        .map(|amounts_node| {
+            let (total_offering_amount, is_total_offering_amount_indefinite) =
+                get_amount_or_indefinite(&amounts_node, "totalOfferingAmount")?;
+            let total_amount_sold = get_optional_int64(&amounts_node, "totalAmountSold")?;
+            let (total_remaining, is_total_remaining_indefinite) =
+                get_amount_or_indefinite(&amounts_node, "totalRemaining")?;
+            let clarification_of_response =
+                get_string(&amounts_node, "clarificationOfResponse").ok();
+
+            Ok(OfferingSalesAmounts {
+                total_offering_amount,
+                is_total_offering_amount_indefinite,
+                total_amount_sold,
+                total_remaining,
+                is_total_remaining_indefinite,
+                clarification_of_response,
+            })
+        })
+        .transpose()
+}
+
+fn parse_investors(node: &roxmltree::Node) -> Result<Option<Investors>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("investors"))
+        .map(|investors_node| {
+            let has_non_accredited_investors =
+                get_bool(&investors_node, "hasNonAccreditedInvestors").ok();
+            let number_non_accredited_investors =
+                get_optional_int64(&investors_node, "numberNonAccreditedInvestors")?;
+            let total_number_already_invested =
+                get_optional_int64(&investors_node, "totalNumberAlreadyInvested")?;
+
+            Ok(Investors {
+                has_non_accredited_investors,
+                number_non_accredited_investors,
+                total_number_already_invested,
+            })
+        })
+        .transpose()
+}
+
+fn parse_sales_commissions_finders_fees(
+    node: &roxmltree::Node,
+) -> Result<Option<SalesCommissionsFindersFees>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("salesCommissionsFindersFees"))
+        .map(|fees_node| {
+            let commissions_node = fees_node
+                .children()
+                .find(|node| node.has_tag_name("salesCommissions"));
+            let sales_commissions = commissions_node
+                .map(|node| get_optional_int64(&node, "dollarAmount"))
+                .transpose()?
+                .flatten();
+            let is_sales_commissions_estimate =
+                commissions_node.and_then(|node| get_bool(&node, "isEstimate").ok());
+            let finders_fees_node = fees_node
+                .children()
+                .find(|node| node.has_tag_name("findersFees"));
+            let finders_fees = finders_fees_node
+                .map(|node| get_optional_int64(&node, "dollarAmount"))
+                .transpose()?
+                .flatten();
+            let is_finders_fees_estimate =
+                finders_fees_node.and_then(|node| get_bool(&node, "isEstimate").ok());
+            let clarification_of_response = get_string(&fees_node, "clarificationOfResponse").ok();
+
+            Ok(SalesCommissionsFindersFees {
+                sales_commissions,
+                is_sales_commissions_estimate,
+                finders_fees,
+                is_finders_fees_estimate,
+                clarification_of_response,
+            })
+        })
+        .transpose()
+}
+
+fn parse_use_of_proceeds(node: &roxmltree::Node) -> Result<Option<UseOfProceeds>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("useOfProceeds"))
+        .map(|proceeds_node| {
+            let gross_proceeds_node = proceeds_node
+                .children()
+                .find(|node| node.has_tag_name("grossProceedsUsed"));
+            let gross_proceeds_used = gross_proceeds_node
+                .map(|node| get_optional_int64(&node, "dollarAmount"))
+                .transpose()?
+                .flatten();
+            let is_estimate =
+                gross_proceeds_node.and_then(|node| get_bool(&node, "isEstimate").ok());
+            let clarification_of_response =
+                get_string(&proceeds_node, "clarificationOfResponse").ok();
+
+            Ok(UseOfProceeds {
+                gross_proceeds_used,
+                is_estimate,
+                clarification_of_response,
+            })
+        })
+        .transpose()
+}
+
+fn parse_signature_block(node: &roxmltree::Node) -> Result<Option<SignatureBlock>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("signatureBlock"))
+        .map(|signature_block_node| {
+            let authorized_representative =
+                get_bool(&signature_block_node, "authorizedRepresentative").ok();
+            let signatures = signature_block_node
+                .children()
+                .filter(|node| node.has_tag_name("signature"))
+                .map(|signature_node| Signature {
+                    issuer_name: get_string(&signature_node, "issuerName").ok(),
+                    signature_name: get_string(&signature_node, "signatureName").ok(),
+                    name_of_signer: get_string(&signature_node, "nameOfSigner").ok(),
+                    signature_title: get_string(&signature_node, "signatureTitle").ok(),
+                    signature_date: get_string(&signature_node, "signatureDate").ok(),
+                })
+                .collect();
+
+            Ok(SignatureBlock {
+                authorized_representative,
+                signatures,
+            })
+        })
+        .transpose()
+}
+
+fn get_list(node: &roxmltree::Node, tag: &str) -> Vec<String> {
+    node.children()
+        .filter(|node| node.has_tag_name(tag))
+        .flat_map(|node| node.children())
+        .filter(|node| node.is_element())
+        .filter_map(|node| node.text())
+        .map(|s| s.trim().to_string())
+        .collect()
+}
+
+fn get_amount_or_indefinite(
+    node: &roxmltree::Node,
+    tag: &str,
+) -> Result<(Option<i64>, bool), String> {
+    match get_string(node, tag) {
+        Ok(text) if text.trim().eq_ignore_ascii_case("indefinite") => Ok((None, true)),
+        Ok(_) => get_int64(node, tag).map(|amount| (Some(amount), false)),
+        Err(_) => Ok((None, false)),
+    }
+}
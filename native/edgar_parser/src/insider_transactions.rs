@@ -0,0 +1,507 @@
+use crate::ownership::{
+    Document, OwnershipNature, PostTransactionAmounts, TransactionCoding, ValueFootnote,
+};
+use crate::xml::Value;
+use rustler::NifMap;
+
+// https://www.sec.gov/about/forms/form4data.pdf
+// Transactions from both tables are flattened into one record each. Open
+// market transactions are the P and S codes; everything else (grants,
+// exercises, tax withholding, gifts...) is a non-market transaction.
+//
+// Details that filers only disclose in footnotes are parsed from the footnotes
+// each transaction cites: the price range behind a weighted average price,
+// the adoption date of a 10b5-1 plan and how indirectly owned shares are held.
+
+#[derive(NifMap)]
+pub struct InsiderTransaction {
+    document_type: String,
+    period_of_report: Option<String>,
+    issuer_cik: String,
+    issuer_trading_symbol: Option<String>,
+    reporting_owner_cik: String,
+    reporting_owner_name: Option<String>,
+    is_derivative: bool,
+    security_title: Option<String>,
+    transaction_date: Option<String>,
+    transaction_code: Option<String>,
+    transaction_description: Option<String>,
+    equity_swap_involved: Option<bool>,
+    acquired_disposed_code: Option<String>,
+    shares: Option<f64>,
+    share_change: Option<f64>,
+    price_per_share: Option<f64>,
+    value: Option<f64>,
+    shares_owned_following_transaction: Option<f64>,
+    direct_or_indirect_ownership: Option<String>,
+    nature_of_ownership: Option<String>,
+    conversion_or_exercise_price: Option<f64>,
+    exercise_date: Option<String>,
+    expiration_date: Option<String>,
+    underlying_security_title: Option<String>,
+    underlying_security_shares: Option<f64>,
+    is_open_market: bool,
+    is_10b5_1_plan: bool,
+    price_range_low: Option<f64>,
+    price_range_high: Option<f64>,
+    plan_adoption_date: Option<String>,
+    indirect_ownership_description: Option<String>,
+    trust_name: Option<String>,
+}
+
+#[rustler::nif]
+pub fn normalize_insider_transactions(document: Document) -> Vec<InsiderTransaction> {
+    let mut transactions = vec![];
+
+    for transaction in document
+        .non_derivative_table
+        .iter()
+        .flat_map(|table| table.transactions.iter())
+    {
+        let amounts = transaction.transaction_amounts.as_ref();
+        let footnote_ids = [
+            transaction.security_title.as_ref(),
+            transaction.transaction_date.as_ref(),
+            transaction.deemed_execution_date.as_ref(),
+            transaction.transaction_timeliness.as_ref(),
+            amounts.and_then(|a| a.shares.as_ref()),
+            amounts.and_then(|a| a.price_per_share.as_ref()),
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|value| value.footnote_ids.iter().cloned())
+        .collect();
+
+        transactions.push(build_transaction(
+            &document,
+            Fields {
+                is_derivative: false,
+                security_title: transaction.security_title.as_ref(),
+                transaction_date: transaction.transaction_date.as_ref(),
+                transaction_coding: transaction.transaction_coding.as_ref(),
+                shares: amounts.and_then(|a| a.shares.as_ref()),
+                price_per_share: amounts.and_then(|a| a.price_per_share.as_ref()),
+                total_value: None,
+                acquired_disposed_code: amounts.and_then(|a| a.acquired_disposed_code.as_ref()),
+                post_transaction_amounts: transaction.post_transaction_amounts.as_ref(),
+                ownership_nature: transaction.ownership_nature.as_ref(),
+                footnote_ids,
+            },
+        ));
+    }
+
+    for transaction in document
+        .derivative_table
+        .iter()
+        .flat_map(|table| table.transactions.iter())
+    {
+        let amounts = transaction.transaction_amounts.as_ref();
+        let underlying = transaction.underlying_security.as_ref();
+        let footnote_ids = [
+            transaction.security_title.as_ref(),
+            transaction.conversion_or_exercise_price.as_ref(),
+            transaction.transaction_date.as_ref(),
+            transaction.deemed_execution_date.as_ref(),
+            transaction.transaction_timeliness.as_ref(),
+            amounts.and_then(|a| a.shares.as_ref()),
+            amounts.and_then(|a| a.price_per_share.as_ref()),
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|value| value.footnote_ids.iter().cloned())
+        .collect();
+
+        let mut normalized = build_transaction(
+            &document,
+            Fields {
+                is_derivative: true,
+                security_title: transaction.security_title.as_ref(),
+                transaction_date: transaction.transaction_date.as_ref(),
+                transaction_coding: transaction.transaction_coding.as_ref(),
+                shares: amounts.and_then(|a| a.shares.as_ref()),
+                price_per_share: amounts.and_then(|a| a.price_per_share.as_ref()),
+                total_value: amounts.and_then(|a| a.total_value.as_ref()),
+                acquired_disposed_code: amounts.and_then(|a| a.acquired_disposed_code.as_ref()),
+                post_transaction_amounts: transaction.post_transaction_amounts.as_ref(),
+                ownership_nature: transaction.ownership_nature.as_ref(),
+                footnote_ids,
+            },
+        );
+        normalized.conversion_or_exercise_price =
+            get_number(transaction.conversion_or_exercise_price.as_ref());
+        normalized.exercise_date = get_text(transaction.exercise_date.as_ref());
+        normalized.expiration_date = get_text(transaction.expiration_date.as_ref());
+        normalized.underlying_security_title = get_text(underlying.and_then(|u| u.title.as_ref()));
+        normalized.underlying_security_shares =
+            get_number(underlying.and_then(|u| u.shares.as_ref()));
+        transactions.push(normalized);
+    }
+
+    transactions
+}
+
+struct Fields<'a> {
+    is_derivative: bool,
+    security_title: Option<&'a ValueFootnote>,
+    transaction_date: Option<&'a ValueFootnote>,
+    transaction_coding: Option<&'a TransactionCoding>,
+    shares: Option<&'a ValueFootnote>,
+    price_per_share: Option<&'a ValueFootnote>,
+    total_value: Option<&'a ValueFootnote>,
+    acquired_disposed_code: Option<&'a ValueFootnote>,
+    post_transaction_amounts: Option<&'a PostTransactionAmounts>,
+    ownership_nature: Option<&'a OwnershipNature>,
+    footnote_ids: Vec<String>,
+}
+
+fn build_transaction(document: &Document, fields: Fields) -> InsiderTransaction {
+    let coding = fields.transaction_coding;
+    let transaction_code = coding
+        .and_then(|coding| coding.transaction_code.as_ref())
+        .map(|code| code.trim().to_uppercase());
+    let acquired_disposed_code = get_text(fields.acquired_disposed_code).map(|c| c.to_uppercase());
+    let shares = get_number(fields.shares);
+    let price_per_share = get_number(fields.price_per_share);
+
+    let share_change = match (shares, acquired_disposed_code.as_deref()) {
+        (Some(shares), Some("A")) => Some(shares),
+        (Some(shares), Some("D")) => Some(-shares),
+        _ => None,
+    };
+    let value = get_number(fields.total_value).or(match (shares, price_per_share) {
+        (Some(shares), Some(price)) => Some(shares * price),
+        _ => None,
+    });
+
+    let mut footnote_ids = fields.footnote_ids;
+    footnote_ids.extend(
+        coding
+            .iter()
+            .flat_map(|coding| coding.footnote_ids.iter().cloned()),
+    );
+    let transaction_notes = footnote_notes(document, &footnote_ids);
+    // Plans are disclosed by the aff10b5One checkbox on filings since 2023,
+    // and in footnotes before that. The checkbox covers the whole filing, so
+    // it only marks the open market trades, not grants or withholdings that
+    // are reported next to them.
+    let is_open_market = matches!(transaction_code.as_deref(), Some("P" | "S"));
+    let is_10b5_1_plan = (is_open_market && document.aff10b5_one == Some(true))
+        || transaction_notes.iter().any(|note| mentions_10b5_1(note));
+    let plan_adoption_date = transaction_notes
+        .iter()
+        .find_map(|note| parse_plan_adoption_date(note));
+
+    // The range is usually cited by the price itself, but some filers cite it
+    // on the transaction code or date instead.
+    let price_notes = fields
+        .price_per_share
+        .map(|price| footnote_notes(document, &price.footnote_ids))
+        .filter(|notes| !notes.is_empty())
+        .unwrap_or(transaction_notes);
+    let price_range = price_notes.iter().find_map(|note| parse_price_range(note));
+
+    let ownership_nature = fields.ownership_nature;
+    let direct_or_indirect_ownership =
+        get_text(ownership_nature.and_then(|nature| nature.direct_or_indirect_ownership.as_ref()));
+    let indirect_ownership_description = if direct_or_indirect_ownership.as_deref() == Some("I") {
+        let nature_ids: Vec<String> = ownership_nature
+            .iter()
+            .flat_map(|nature| {
+                [
+                    nature.direct_or_indirect_ownership.as_ref(),
+                    nature.nature_of_ownership.as_ref(),
+                ]
+            })
+            .flatten()
+            .flat_map(|value| value.footnote_ids.iter().cloned())
+            .collect();
+        footnote_notes(document, &nature_ids)
+            .iter()
+            .find_map(|note| trust_sentence(note))
+    } else {
+        None
+    };
+    let trust_name = indirect_ownership_description
+        .as_deref()
+        .and_then(parse_trust_name);
+
+    InsiderTransaction {
+        document_type: document.document_type.clone(),
+        period_of_report: document.period_of_report.clone(),
+        issuer_cik: document.issuer.cik.clone(),
+        issuer_trading_symbol: document.issuer.trading_symbol.clone(),
+        reporting_owner_cik: document.reporting_owner.id.cik.clone(),
+        reporting_owner_name: document.reporting_owner.id.name.clone(),
+        is_derivative: fields.is_derivative,
+        security_title: get_text(fields.security_title),
+        transaction_date: get_text(fields.transaction_date),
+        transaction_description: transaction_code
+            .as_deref()
+            .and_then(transaction_description)
+            .map(|description| description.to_string()),
+        is_open_market,
+        transaction_code,
+        equity_swap_involved: coding.and_then(|coding| coding.equity_swap_involved),
+        acquired_disposed_code,
+        shares,
+        share_change,
+        price_per_share,
+        value,
+        shares_owned_following_transaction: get_number(
+            fields
+                .post_transaction_amounts
+                .and_then(|amounts| amounts.shares_owned_following_transaction.as_ref()),
+        ),
+        direct_or_indirect_ownership,
+        nature_of_ownership: get_text(
+            ownership_nature.and_then(|nature| nature.nature_of_ownership.as_ref()),
+        ),
+        conversion_or_exercise_price: None,
+        exercise_date: None,
+        expiration_date: None,
+        underlying_security_title: None,
+        underlying_security_shares: None,
+        is_10b5_1_plan,
+        price_range_low: price_range.map(|(low, _)| low),
+        price_range_high: price_range.map(|(_, high)| high),
+        plan_adoption_date,
+        indirect_ownership_description,
+        trust_name,
+    }
+}
+
+fn transaction_description(code: &str) -> Option<&'static str> {
+    let description = match code {
+        "P" => "Open market or private purchase",
+        "S" => "Open market or private sale",
+        "V" => "Transaction voluntarily reported earlier than required",
+        "A" => "Grant, award or other acquisition",
+        "D" => "Disposition to the issuer",
+        "F" => "Payment of exercise price or tax liability by delivering or withholding securities",
+        "I" => "Discretionary transaction",
+        "M" => "Exercise or conversion of derivative security exempted pursuant to Rule 16b-3",
+        "C" => "Conversion of derivative security",
+        "E" => "Expiration of short derivative position",
+        "H" => "Expiration or cancellation of long derivative position with value received",
+        "O" => "Exercise of out-of-the-money derivative security",
+        "X" => "Exercise of in-the-money or at-the-money derivative security",
+        "G" => "Bona fide gift",
+        "L" => "Small acquisition under Rule 16a-6",
+        "W" => "Acquisition or disposition by will or the laws of descent and distribution",
+        "Z" => "Deposit into or withdrawal from voting trust",
+        "J" => "Other acquisition or disposition",
+        "K" => "Transaction in equity swap or instrument with similar characteristics",
+        "U" => "Disposition pursuant to a tender of shares in a change of control transaction",
+        _ => return None,
+    };
+    Some(description)
+}
+
+// Matches "10b5-1" as well as the "10b5 1" and en dash spellings, unless the
+// sentence negates it first ("not made pursuant to a Rule 10b5-1 plan").
+fn mentions_10b5_1(note: &str) -> bool {
+    sentences(note).iter().any(|sentence| {
+        let sentence = sentence
+            .to_lowercase()
+            .replace(['\u{2013}', '\u{2011}', ' '], "-")
+            .replace('\u{2019}', "'");
+        let Some(index) = sentence.find("10b5-1").or_else(|| sentence.find("10b-5-1")) else {
+            return false;
+        };
+
+        !sentence[..index]
+            .split(|c: char| !c.is_alphanumeric() && c != '\'')
+            .any(|word| {
+                matches!(
+                    word,
+                    "not" | "no" | "nor" | "neither" | "outside" | "without"
+                ) || word.ends_with("n't")
+            })
+    })
+}
+
+fn footnote_notes<'a>(document: &'a Document, ids: &[String]) -> Vec<&'a str> {
+    ids.iter()
+        .filter_map(|id| {
+            document
+                .footnotes
+                .iter()
+                .find(|footnote| footnote.id.as_ref() == Some(id))
+        })
+        .filter_map(|footnote| footnote.note.as_deref())
+        .collect()
+}
+
+// "... at prices ranging from $10.01 to $10.45, inclusive." The amounts after
+// the word range or ranging are used, so other dollar figures earlier in the
+// note (such as the weighted average itself) are skipped, and words such as
+// "arrangement" are not mistaken for it. Only ASCII is lowercased so the
+// offset found is still valid in the original note.
+fn parse_price_range(note: &str) -> Option<(f64, f64)> {
+    let lower = note.to_ascii_lowercase();
+    let start = lower
+        .match_indices("rang")
+        .map(|(index, _)| index)
+        .find(|&index| {
+            let word: String = lower[index..]
+                .chars()
+                .take_while(|c| c.is_alphanumeric())
+                .collect();
+            !lower[..index].ends_with(char::is_alphanumeric)
+                && matches!(word.as_str(), "range" | "ranges" | "ranging")
+        })?;
+    let amounts: Vec<f64> = note[start..]
+        .split('$')
+        .skip(1)
+        .filter_map(|part| {
+            let amount: String = part
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+                .filter(|c| *c != ',')
+                .collect();
+            amount.trim_end_matches('.').parse().ok()
+        })
+        .take(2)
+        .collect();
+
+    match amounts[..] {
+        [first, second] => Some((first.min(second), first.max(second))),
+        _ => None,
+    }
+}
+
+// "... pursuant to a Rule 10b5-1 trading plan adopted by the reporting person
+// on May 11, 2022." Dates are returned as YYYY-MM-DD.
+fn parse_plan_adoption_date(note: &str) -> Option<String> {
+    if !mentions_10b5_1(note) {
+        return None;
+    }
+    let start = note.to_ascii_lowercase().find("adopt")?;
+    parse_first_date(&note[start..])
+}
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+// Finds the first date written as "May 11, 2022", "5/11/2022" or "2022-05-11".
+fn parse_first_date(text: &str) -> Option<String> {
+    let words: Vec<&str> = text
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| matches!(c, ',' | '.' | ';' | ')' | '(')))
+        .collect();
+
+    words.iter().enumerate().find_map(|(index, word)| {
+        let numeric: Vec<u32> = word
+            .split(['/', '-'])
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()
+            .unwrap_or_default();
+        let (year, month, day) = match numeric[..] {
+            [year, month, day] if word.contains('-') && year > 1900 => (year, month, day),
+            [month, day, year] if word.contains('/') && year > 1900 => (year, month, day),
+            _ => {
+                let month = MONTHS
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(word))?;
+                let day = words.get(index + 1)?.parse().ok()?;
+                let year = words.get(index + 2)?.parse().ok()?;
+                (year, month as u32 + 1, day)
+            }
+        };
+
+        ((1..=12).contains(&month) && (1..=31).contains(&day))
+            .then(|| format!("{year:04}-{month:02}-{day:02}"))
+    })
+}
+
+// The sentence of an ownership footnote that says the shares are held
+// through a trust, e.g. "These shares are held by the Smith Family Trust, of
+// which the reporting person is a trustee."
+fn trust_sentence(note: &str) -> Option<String> {
+    sentences(note)
+        .into_iter()
+        .find(|sentence| {
+            sentence
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| word.eq_ignore_ascii_case("trust"))
+        })
+        .map(|sentence| sentence.to_string())
+}
+
+// Sentences end at a period followed by whitespace, except after initials
+// and short abbreviations such as "John A. Smith" or "Mr. Smith".
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = vec![];
+    let mut start = 0;
+
+    for (index, _) in text.match_indices(". ") {
+        let word = text[start..index].rsplit(' ').next().unwrap_or_default();
+        if word.len() > 2 {
+            sentences.push(text[start..=index].trim());
+            start = index + 1;
+        }
+    }
+    sentences.push(text[start..].trim());
+
+    sentences.retain(|sentence| !sentence.is_empty());
+    sentences
+}
+
+// The capitalized words leading up to "Trust", e.g. "Smith Family Trust" or
+// "John A. Smith 2010 Revocable Trust".
+fn parse_trust_name(sentence: &str) -> Option<String> {
+    let words: Vec<&str> = sentence.split_whitespace().collect();
+    let end = words
+        .iter()
+        .position(|word| word.trim_end_matches([',', '.', ';', ')']) == "Trust")?;
+
+    let start = words[..end]
+        .iter()
+        .rposition(|word| {
+            !word.starts_with(|c: char| c.is_uppercase() || c.is_ascii_digit())
+                || word.eq_ignore_ascii_case("the")
+        })
+        .map_or(0, |index| index + 1);
+
+    (start < end).then(|| {
+        let mut name = words[start..=end].join(" ");
+        name.truncate(name.rfind("Trust").unwrap_or(0) + "Trust".len());
+        name
+    })
+}
+
+fn get_text(value: Option<&ValueFootnote>) -> Option<String> {
+    let text = match value?.value.as_ref()? {
+        Value::Int(value) => value.to_string(),
+        Value::Float(value) => value.to_string(),
+        Value::Text(text) => text.trim().to_string(),
+        Value::Bool(value) => value.to_string(),
+    };
+    Some(text).filter(|text| !text.is_empty())
+}
+
+fn get_number(value: Option<&ValueFootnote>) -> Option<f64> {
+    match value?.value.as_ref()? {
+        Value::Int(value) => Some(*value as f64),
+        Value::Float(value) => Some(*value),
+        Value::Text(text) => text
+            .trim()
+            .trim_start_matches('$')
+            .replace(',', "")
+            .parse()
+            .ok(),
+        Value::Bool(_) => None,
+    }
+}
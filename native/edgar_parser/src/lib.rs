@@ -1,24 +1,84 @@
+mod adv;
+mod company_facts;
+mod cover_page;
 mod feeds;
+mod filing_index;
+mod filing_summary;
+mod fiscal_periods;
+mod form144;
+mod formc;
+mod formd;
+mod insider_transactions;
+mod nmfp;
+mod nport;
+mod npx;
 mod ownership;
+mod ratios;
+mod schedule13;
+mod standardize;
+mod submissions;
 mod thirteenf;
 mod xbrl;
 mod xml;
 
+use adv::parse_adv_feed;
+use company_facts::normalize_company_facts;
+use cover_page::extract_cover_page;
 use feeds::{parse_company_feed, parse_current_feed, parse_filing_feed, parse_rss_feed};
-use ownership::parse_ownership_form;
-use thirteenf::{parse_form13f_document, parse_form13f_table};
+use filing_index::{parse_filing_index, parse_filing_index_headers};
+use filing_summary::{group_facts_by_report, parse_filing_summary, parse_presentation_linkbase};
+use fiscal_periods::{classify_fiscal_periods, derive_fiscal_values};
+use form144::parse_form144;
+use formc::parse_formc;
+use formd::parse_formd;
+use insider_transactions::normalize_insider_transactions;
+use nmfp::parse_nmfp;
+use nport::parse_nport;
+use npx::{parse_npx_document, parse_npx_table};
+use ownership::{merge_ownership_amendments, parse_ownership_form};
+use ratios::{compute_ratios, default_ratios};
+use schedule13::parse_schedule13;
+use standardize::{default_line_items, standardize_statements};
+use submissions::parse_submissions;
+use thirteenf::{check_form13f_consistency, parse_form13f_document, parse_form13f_table};
 use xbrl::parse_xbrl;
 
 rustler::init!(
     "Elixir.EDGAR.Native",
     [
+        check_form13f_consistency,
+        classify_fiscal_periods,
+        compute_ratios,
+        default_line_items,
+        default_ratios,
+        derive_fiscal_values,
+        extract_cover_page,
+        group_facts_by_report,
+        merge_ownership_amendments,
+        normalize_company_facts,
+        normalize_insider_transactions,
+        parse_adv_feed,
         parse_company_feed,
         parse_current_feed,
         parse_filing_feed,
+        parse_filing_index,
+        parse_filing_index_headers,
+        parse_filing_summary,
         parse_form13f_document,
         parse_form13f_table,
+        parse_form144,
+        parse_formc,
+        parse_formd,
+        parse_nmfp,
+        parse_nport,
+        parse_npx_document,
+        parse_npx_table,
         parse_ownership_form,
+        parse_presentation_linkbase,
         parse_rss_feed,
+        parse_schedule13,
+        parse_submissions,
         parse_xbrl,
+        standardize_statements,
     ]
 );
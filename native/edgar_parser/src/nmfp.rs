@@ -0,0 +1,381 @@
+use crate::xml::{
+    get_bool, get_int32, get_optional_float64, get_string, parse_filer, parse_float64, Filer,
+};
+use roxmltree::Document as XMLDoc;
+use rustler::NifMap;
+
+// https://www.sec.gov/info/edgar/specifications/form-n-mfp3-xml-tech-specs
+// N-MFP2 and N-MFP3 share most element names. N-MFP2 reports yields and
+// liquidity once per week while N-MFP3 reports them for every business day,
+// so those fields are lists in both cases.
+
+#[derive(NifMap)]
+pub struct Document {
+    schema_version: Option<String>,
+    submission_type: String,
+    filer: Option<Filer>,
+    general_info: GeneralInfo,
+    series_level_info: SeriesLevelInfo,
+    class_level_info: Vec<ClassLevelInfo>,
+    schedule_of_portfolio_securities: Table,
+}
+
+#[derive(NifMap)]
+pub struct GeneralInfo {
+    report_date: String,
+    registrant_cik: Option<String>,
+    registrant_name: Option<String>,
+    registrant_lei: Option<String>,
+    series_id: Option<String>,
+    series_name: Option<String>,
+    series_lei: Option<String>,
+    total_share_classes_in_series: Option<i32>,
+    is_final_filing: Option<bool>,
+}
+
+#[derive(NifMap)]
+pub struct SeriesLevelInfo {
+    securities_act_file_number: Option<String>,
+    money_market_fund_category: Option<String>,
+    weighted_average_maturity: Option<i32>,
+    weighted_average_life: Option<i32>,
+    total_value_portfolio_securities: Option<f64>,
+    amortized_cost_portfolio_securities: Option<f64>,
+    cash: Option<f64>,
+    total_value_other_assets: Option<f64>,
+    total_value_liabilities: Option<f64>,
+    net_assets: Option<f64>,
+    shares_outstanding: Option<f64>,
+    seven_day_gross_yields: Vec<DatedValue>,
+    liquidity: Liquidity,
+}
+
+#[derive(NifMap)]
+pub struct ClassLevelInfo {
+    class_id: Option<String>,
+    min_initial_investment: Option<f64>,
+    net_assets: Option<f64>,
+    shares_outstanding: Option<f64>,
+    seven_day_net_yields: Vec<DatedValue>,
+}
+
+#[derive(NifMap)]
+pub struct DatedValue {
+    date: Option<String>,
+    label: Option<String>,
+    value: f64,
+}
+
+#[derive(NifMap)]
+pub struct Liquidity {
+    daily_liquid_assets: Vec<DatedValue>,
+    weekly_liquid_assets: Vec<DatedValue>,
+    percentage_daily_liquid_assets: Vec<DatedValue>,
+    percentage_weekly_liquid_assets: Vec<DatedValue>,
+}
+
+#[derive(NifMap)]
+pub struct Table {
+    entries: Vec<TableEntry>,
+}
+
+#[derive(NifMap)]
+pub struct TableEntry {
+    name_of_issuer: String,
+    title_of_issuer: Option<String>,
+    cusip: Option<String>,
+    lei: Option<String>,
+    isin: Option<String>,
+    investment_category: Option<String>,
+    maturity_date_wam: Option<String>,
+    maturity_date_wal: Option<String>,
+    final_legal_maturity_date: Option<String>,
+    has_demand_feature: Option<bool>,
+    has_guarantee: Option<bool>,
+    has_enhancements: Option<bool>,
+    yield_as_of_reporting_date: Option<f64>,
+    value_including_sponsor_support: Option<f64>,
+    value_excluding_sponsor_support: Option<f64>,
+    percentage_of_net_assets: Option<f64>,
+    is_level_3: Option<bool>,
+    is_daily_liquid_asset: Option<bool>,
+    is_weekly_liquid_asset: Option<bool>,
+    is_illiquid: Option<bool>,
+}
+
+#[rustler::nif]
+pub fn parse_nmfp(xml: &str) -> Result<Document, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+    let schema_version = get_string(&root_node, "schemaVersion").ok();
+
+    let header_data_node = root_node
+        .children()
+        .find(|node| node.has_tag_name("headerData"))
+        .ok_or("headerData not found".to_string())?;
+    let submission_type = get_string(&header_data_node, "submissionType")?;
+    let filer = parse_filer(&header_data_node)?;
+
+    let form_data_node = root_node
+        .children()
+        .find(|node| node.has_tag_name("formData"))
+        .ok_or("formData not found".to_string())?;
+    let general_info = parse_general_info(&form_data_node)?;
+    let series_level_info = parse_series_level_info(&form_data_node)?;
+    let class_level_info = parse_class_level_info(&form_data_node)?;
+    let schedule_of_portfolio_securities = parse_schedule(&form_data_node)?;
+
+    Ok(Document {
+        schema_version,
+        submission_type,
+        filer,
+        general_info,
+        series_level_info,
+        class_level_info,
+        schedule_of_portfolio_securities,
+    })
+}
+
+fn parse_general_info(node: &roxmltree::Node) -> Result<GeneralInfo, String> {
+    node.children()
+        .find(|node| node.has_tag_name("generalInfo"))
+        .ok_or("generalInfo not found".to_string())
+        .and_then(|general_info_node| {
+            let report_date = get_string(&general_info_node, "reportDate")?;
+            let registrant_cik = get_string(&general_info_node, "cik")
+                .or_else(|_| get_string(&general_info_node, "registrantCIK"))
+                .ok();
+            let registrant_name = get_string(&general_info_node, "registrantFullName").ok();
+            let registrant_lei = get_string(&general_info_node, "registrantLEIId").ok();
+            let series_id = get_string(&general_info_node, "seriesId").ok();
+            let series_name = get_string(&general_info_node, "nameOfSeries").ok();
+            let series_lei = get_string(&general_info_node, "leiOfSeries").ok();
+            let total_share_classes_in_series =
+                get_int32(&general_info_node, "totalShareClassesInSeries").ok();
+            let is_final_filing = get_bool(&general_info_node, "finalFilingFlag").ok();
+
+            Ok(GeneralInfo {
+                report_date,
+                registrant_cik,
+                registrant_name,
+                registrant_lei,
+                series_id,
+                series_name,
+                series_lei,
+                total_share_classes_in_series,
+                is_final_filing,
+            })
+        })
+}
+
+fn parse_series_level_info(node: &roxmltree::Node) -> Result<SeriesLevelInfo, String> {
+    node.children()
+        .find(|node| node.has_tag_name("seriesLevelInfo"))
+        .ok_or("seriesLevelInfo not found".to_string())
+        .and_then(|series_node| {
+            let securities_act_file_number =
+                get_string(&series_node, "securitiesActFileNumber").ok();
+            let money_market_fund_category =
+                get_string(&series_node, "moneyMarketFundCategory").ok();
+            let weighted_average_maturity =
+                get_optional_int32(&series_node, "averagePortfolioMaturity")?;
+            let weighted_average_life = get_optional_int32(&series_node, "averageLifeMaturity")?;
+            let total_value_portfolio_securities =
+                get_optional_float64(&series_node, "totalValuePortfolioSecurities")?;
+            let amortized_cost_portfolio_securities =
+                get_optional_float64(&series_node, "amortizedCostPortfolioSecurities")?;
+            let cash = get_optional_float64(&series_node, "cash")?;
+            let total_value_other_assets =
+                get_optional_float64(&series_node, "totalValueOtherAssets")?;
+            let total_value_liabilities =
+                get_optional_float64(&series_node, "totalValueLiabilities")?;
+            let net_assets = get_optional_float64(&series_node, "netAssetOfSeries")?;
+            let shares_outstanding =
+                get_optional_float64(&series_node, "numberOfSharesOutstanding")?;
+            let seven_day_gross_yields = get_dated_values(&series_node, "sevenDayGrossYield")?;
+            let liquidity = parse_liquidity(&series_node)?;
+
+            Ok(SeriesLevelInfo {
+                securities_act_file_number,
+                money_market_fund_category,
+                weighted_average_maturity,
+                weighted_average_life,
+                total_value_portfolio_securities,
+                amortized_cost_portfolio_securities,
+                cash,
+                total_value_other_assets,
+                total_value_liabilities,
+                net_assets,
+                shares_outstanding,
+                seven_day_gross_yields,
+                liquidity,
+            })
+        })
+}
+
+fn parse_liquidity(node: &roxmltree::Node) -> Result<Liquidity, String> {
+    let liquidity_node = node
+        .children()
+        .find(|node| node.has_tag_name("liquidAssetsDetails"))
+        .unwrap_or(*node);
+
+    Ok(Liquidity {
+        daily_liquid_assets: get_dated_values(&liquidity_node, "totalValueDailyLiquidAssets")?,
+        weekly_liquid_assets: get_dated_values(&liquidity_node, "totalValueWeeklyLiquidAssets")?,
+        percentage_daily_liquid_assets: get_dated_values(
+            &liquidity_node,
+            "percentageDailyLiquidAssets",
+        )?,
+        percentage_weekly_liquid_assets: get_dated_values(
+            &liquidity_node,
+            "percentageWeeklyLiquidAssets",
+        )?,
+    })
+}
+
+fn parse_class_level_info(node: &roxmltree::Node) -> Result<Vec<ClassLevelInfo>, String> {
+    node.children()
+        .filter(|node| node.has_tag_name("classLevelInfo"))
+        .enumerate()
+        .map(|(index, class_node)| {
+            parse_class(&class_node).map_err(|e| format!("classLevelInfo {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+fn parse_class(node: &roxmltree::Node) -> Result<ClassLevelInfo, String> {
+    let class_id = get_string(node, "classesId").ok();
+    let min_initial_investment = get_optional_float64(node, "minInitialInvestment")?;
+    let net_assets = get_optional_float64(node, "netAssetsOfClass")?;
+    let shares_outstanding = get_optional_float64(node, "numberOfSharesOutstanding")?;
+    let seven_day_net_yields = get_dated_values(node, "sevenDayNetYield")?;
+
+    Ok(ClassLevelInfo {
+        class_id,
+        min_initial_investment,
+        net_assets,
+        shares_outstanding,
+        seven_day_net_yields,
+    })
+}
+
+fn parse_schedule(node: &roxmltree::Node) -> Result<Table, String> {
+    let entries = node
+        .children()
+        .filter(|node| node.has_tag_name("scheduleOfPortfolioSecuritiesInfo"))
+        .enumerate()
+        .map(|(index, security_node)| {
+            parse_table_entry(&security_node)
+                .map_err(|e| format!("scheduleOfPortfolioSecuritiesInfo {}: {}", index + 1, e))
+        })
+        .collect::<Result<Vec<TableEntry>, String>>()?;
+
+    Ok(Table { entries })
+}
+
+fn parse_table_entry(node: &roxmltree::Node) -> Result<TableEntry, String> {
+    let name_of_issuer = get_string(node, "nameOfIssuer")?;
+    let title_of_issuer = get_string(node, "titleOfIssuer").ok();
+    let cusip = get_string(node, "CUSIPMember").ok();
+    let lei = get_string(node, "LEIID").ok();
+    let isin = get_string(node, "ISINId").ok();
+    let investment_category = get_string(node, "investmentCategory").ok();
+    let maturity_date_wam = get_string(node, "investmentMaturityDateWAM").ok();
+    let maturity_date_wal = get_string(node, "investmentMaturityDateWAL").ok();
+    let final_legal_maturity_date = get_string(node, "finalLegalInvestmentMaturityDate").ok();
+    let has_demand_feature = get_bool(node, "securityDemandFeatureFlag").ok();
+    let has_guarantee = get_bool(node, "securityGuaranteeFlag").ok();
+    let has_enhancements = get_bool(node, "securityEnhancementsFlag").ok();
+    let yield_as_of_reporting_date =
+        get_optional_float64(node, "yieldOfTheSecurityAsOfReportingDate")?;
+    let value_including_sponsor_support =
+        get_optional_float64(node, "includingValueOfAnySponsorSupport")?;
+    let value_excluding_sponsor_support =
+        get_optional_float64(node, "excludingValueOfAnySponsorSupport")?;
+    let percentage_of_net_assets =
+        get_optional_float64(node, "percentageOfMoneyMarketFundNetAssets")?;
+    let is_level_3 = get_bool(node, "securityCategorizedAtLevel3Flag").ok();
+    let is_daily_liquid_asset = get_bool(node, "dailyLiquidAssetSecurityFlag").ok();
+    let is_weekly_liquid_asset = get_bool(node, "weeklyLiquidAssetSecurityFlag").ok();
+    let is_illiquid = get_bool(node, "illiquidSecurityFlag").ok();
+
+    Ok(TableEntry {
+        name_of_issuer,
+        title_of_issuer,
+        cusip,
+        lei,
+        isin,
+        investment_category,
+        maturity_date_wam,
+        maturity_date_wal,
+        final_legal_maturity_date,
+        has_demand_feature,
+        has_guarantee,
+        has_enhancements,
+        yield_as_of_reporting_date,
+        value_including_sponsor_support,
+        value_excluding_sponsor_support,
+        percentage_of_net_assets,
+        is_level_3,
+        is_daily_liquid_asset,
+        is_weekly_liquid_asset,
+        is_illiquid,
+    })
+}
+
+// Handles both the N-MFP2 weekly shape, where the tag wraps one child per
+// Friday (e.g. totalValueDailyLiquidAssetsFridayDay1), and the N-MFP3 daily
+// shape, where the tag repeats with a value and a date child. Weekly values
+// carry no date, so they keep their day label (e.g. FridayDay1) instead.
+fn get_dated_values(node: &roxmltree::Node, tag: &str) -> Result<Vec<DatedValue>, String> {
+    let mut values = vec![];
+
+    for value_node in node.children().filter(|node| node.has_tag_name(tag)) {
+        let children: Vec<_> = value_node.children().filter(|n| n.is_element()).collect();
+
+        if children.is_empty() {
+            if let Some(text) = value_node.text() {
+                values.push(DatedValue {
+                    date: None,
+                    label: None,
+                    value: parse_float64(text, tag)?,
+                });
+            }
+            continue;
+        }
+
+        let date = children
+            .iter()
+            .find(|n| n.tag_name().name().ends_with("Date"))
+            .and_then(|n| n.text())
+            .map(|s| s.trim().to_string());
+
+        for child in children
+            .iter()
+            .filter(|n| !n.tag_name().name().ends_with("Date"))
+        {
+            if let Some(text) = child.text() {
+                let name = child.tag_name().name();
+                let label = match date {
+                    Some(_) => None,
+                    None => Some(name.strip_prefix(tag).unwrap_or(name).to_string()),
+                };
+
+                values.push(DatedValue {
+                    date: date.clone(),
+                    label,
+                    value: parse_float64(text, name)?,
+                });
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+fn get_optional_int32(node: &roxmltree::Node, tag: &str) -> Result<Option<i32>, String> {
+    match node.children().find(|node| node.has_tag_name(tag)) {
+        Some(_) => get_int32(node, tag).map(Some),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,813 @@
+use crate::xml::{get_bool, get_float64, get_optional_float64, get_string, parse_float64};
+use roxmltree::Document as XMLDoc;
+use rustler::NifMap;
+
+// https://www.sec.gov/info/edgar/specifications/form-n-port-xml-tech-specs.htm
+
+#[derive(NifMap)]
+pub struct Document {
+    header_data: HeaderData,
+    form_data: FormData,
+}
+
+#[derive(NifMap)]
+pub struct HeaderData {
+    submission_type: String,
+    is_confidential: Option<bool>,
+    filer_info: FilerInfo,
+}
+
+#[derive(NifMap)]
+pub struct FilerInfo {
+    credentials: Credentials,
+    series_class_info: Option<SeriesClassInfo>,
+}
+
+#[derive(NifMap)]
+pub struct Credentials {
+    cik: String,
+    ccc: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SeriesClassInfo {
+    series_id: Option<String>,
+    class_ids: Vec<String>,
+}
+
+#[derive(NifMap)]
+pub struct FormData {
+    general_info: GeneralInfo,
+    fund_info: FundInfo,
+    investments: Vec<Investment>,
+    signature: Option<Signature>,
+}
+
+#[derive(NifMap)]
+pub struct GeneralInfo {
+    registrant_name: String,
+    registrant_file_number: Option<String>,
+    registrant_cik: Option<String>,
+    registrant_lei: Option<String>,
+    registrant_address: Address,
+    registrant_phone: Option<String>,
+    series_name: Option<String>,
+    series_id: Option<String>,
+    series_lei: Option<String>,
+    report_period_end: String,
+    report_period_date: String,
+    is_final_filing: Option<bool>,
+}
+
+#[derive(NifMap)]
+pub struct Address {
+    street1: Option<String>,
+    street2: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    country: Option<String>,
+    zip_code: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct FundInfo {
+    total_assets: f64,
+    total_liabilities: f64,
+    net_assets: f64,
+    assets_attributable_to_misc_securities: Option<f64>,
+    assets_invested: Option<f64>,
+    borrowings: Borrowings,
+    delayed_delivery: Option<f64>,
+    standby_commitment: Option<f64>,
+    liquidation_preference: Option<f64>,
+    cash_not_reported: Option<f64>,
+    is_non_cash_collateral: Option<bool>,
+    return_info: Option<ReturnInfo>,
+    flows: Vec<Flow>,
+}
+
+#[derive(NifMap)]
+pub struct Borrowings {
+    one_year_banks: Option<f64>,
+    one_year_controlled_companies: Option<f64>,
+    one_year_other_affiliates: Option<f64>,
+    one_year_other: Option<f64>,
+    after_one_year_banks: Option<f64>,
+    after_one_year_controlled_companies: Option<f64>,
+    after_one_year_other_affiliates: Option<f64>,
+    after_one_year_other: Option<f64>,
+}
+
+#[derive(NifMap)]
+pub struct ReturnInfo {
+    monthly_total_returns: Vec<MonthlyTotalReturn>,
+    other_monthly_gains: Vec<MonthlyGain>,
+}
+
+#[derive(NifMap)]
+pub struct MonthlyTotalReturn {
+    class_id: Option<String>,
+    month1: Option<f64>,
+    month2: Option<f64>,
+    month3: Option<f64>,
+}
+
+#[derive(NifMap)]
+pub struct MonthlyGain {
+    month: i32,
+    net_realized_gain: Option<f64>,
+    net_unrealized_appreciation: Option<f64>,
+}
+
+#[derive(NifMap)]
+pub struct Flow {
+    month: i32,
+    sales: Option<f64>,
+    reinvestment: Option<f64>,
+    redemption: Option<f64>,
+}
+
+#[derive(NifMap)]
+pub struct Investment {
+    name: Option<String>,
+    lei: Option<String>,
+    title: Option<String>,
+    cusip: Option<String>,
+    identifiers: Identifiers,
+    balance: Option<f64>,
+    units: Option<String>,
+    other_units_description: Option<String>,
+    currency_code: Option<String>,
+    exchange_rate: Option<f64>,
+    value_usd: Option<f64>,
+    percent_value: Option<f64>,
+    payoff_profile: Option<String>,
+    asset_category: Option<String>,
+    asset_category_description: Option<String>,
+    issuer_category: Option<String>,
+    issuer_category_description: Option<String>,
+    investment_country: Option<String>,
+    is_restricted_security: Option<bool>,
+    fair_value_level: Option<String>,
+    debt_security: Option<DebtSecurity>,
+    derivative: Option<Derivative>,
+    security_lending: Option<SecurityLending>,
+}
+
+#[derive(NifMap)]
+pub struct Identifiers {
+    isin: Option<String>,
+    ticker: Option<String>,
+    other: Vec<OtherIdentifier>,
+}
+
+#[derive(NifMap)]
+pub struct OtherIdentifier {
+    description: Option<String>,
+    value: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct DebtSecurity {
+    maturity_date: Option<String>,
+    coupon_kind: Option<String>,
+    annualized_rate: Option<f64>,
+    is_default: Option<bool>,
+    are_interest_payments_in_arrears: Option<bool>,
+    is_paid_in_kind: Option<bool>,
+    is_mandatory_convertible: Option<bool>,
+    is_contingent_convertible: Option<bool>,
+}
+
+#[derive(NifMap)]
+pub struct Derivative {
+    derivative_type: String,
+    derivative_category: Option<String>,
+    counterparties: Vec<Counterparty>,
+    payoff_profile: Option<String>,
+    reference_instrument: Option<ReferenceInstrument>,
+    put_or_call: Option<String>,
+    written_or_purchased: Option<String>,
+    share_number: Option<f64>,
+    exercise_price: Option<f64>,
+    exercise_price_currency_code: Option<String>,
+    expiration_date: Option<String>,
+    termination_date: Option<String>,
+    settlement_date: Option<String>,
+    notional_amount: Option<f64>,
+    currency_code: Option<String>,
+    amount_currency_sold: Option<f64>,
+    currency_sold: Option<String>,
+    amount_currency_purchased: Option<f64>,
+    currency_purchased: Option<String>,
+    delta: Option<f64>,
+    unrealized_appreciation: Option<f64>,
+}
+
+#[derive(NifMap)]
+pub struct Counterparty {
+    name: Option<String>,
+    lei: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct ReferenceInstrument {
+    name: Option<String>,
+    title: Option<String>,
+    identifiers: Option<Identifiers>,
+}
+
+#[derive(NifMap)]
+pub struct SecurityLending {
+    is_cash_collateral: Option<bool>,
+    is_non_cash_collateral: Option<bool>,
+    is_loan_by_fund: Option<bool>,
+}
+
+#[derive(NifMap)]
+pub struct Signature {
+    date_signed: Option<String>,
+    name_of_applicant: Option<String>,
+    signature: Option<String>,
+    signer_name: Option<String>,
+    title: Option<String>,
+}
+
+const DERIVATIVE_TYPES: [&str; 5] = [
+    "fwdDeriv",
+    "futrDeriv",
+    "swapDeriv",
+    "optionSwaptionWarrantDeriv",
+    "othDeriv",
+];
+
+#[rustler::nif]
+pub fn parse_nport(xml: &str) -> Result<Document, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+    let header_data = parse_header_data(&root_node)?;
+    let form_data = parse_form_data(&root_node)?;
+
+    Ok(Document {
+        header_data,
+        form_data,
+    })
+}
+
+fn parse_header_data(node: &roxmltree::Node) -> Result<HeaderData, String> {
+    node.children()
+        .find(|node| node.has_tag_name("headerData"))
+        .ok_or("headerData not found".to_string())
+        .and_then(|header_data_node| {
+            let submission_type = get_string(&header_data_node, "submissionType")?;
+            let is_confidential = get_bool(&header_data_node, "isConfidential").ok();
+            let filer_info = parse_filer_info(&header_data_node)?;
+
+            Ok(HeaderData {
+                submission_type,
+                is_confidential,
+                filer_info,
+            })
+        })
+}
+
+fn parse_filer_info(node: &roxmltree::Node) -> Result<FilerInfo, String> {
+    node.children()
+        .find(|node| node.has_tag_name("filerInfo"))
+        .ok_or("filerInfo not found".to_string())
+        .and_then(|filer_info_node| {
+            let credentials = parse_credentials(&filer_info_node)?;
+            let series_class_info = parse_series_class_info(&filer_info_node)?;
+
+            Ok(FilerInfo {
+                credentials,
+                series_class_info,
+            })
+        })
+}
+
+fn parse_credentials(node: &roxmltree::Node) -> Result<Credentials, String> {
+    node.children()
+        .find(|node| node.has_tag_name("filer"))
+        .and_then(|filer_node| {
+            filer_node
+                .children()
+                .find(|node| node.has_tag_name("issuerCredentials"))
+        })
+        .ok_or("issuerCredentials not found".to_string())
+        .and_then(|credentials_node| {
+            let cik = get_string(&credentials_node, "cik")?;
+            let ccc = get_string(&credentials_node, "ccc").ok();
+
+            Ok(Credentials { cik, ccc })
+        })
+}
+
+fn parse_series_class_info(node: &roxmltree::Node) -> Result<Option<SeriesClassInfo>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("seriesClassInfo"))
+        .map(|series_class_info_node| {
+            let series_id = get_string(&series_class_info_node, "seriesId").ok();
+            let class_ids = series_class_info_node
+                .children()
+                .filter(|node| node.has_tag_name("classId"))
+                .filter_map(|node| node.text())
+                .map(|s| s.to_string())
+                .collect();
+
+            Ok(SeriesClassInfo {
+                series_id,
+                class_ids,
+            })
+        })
+        .transpose()
+}
+
+fn parse_form_data(node: &roxmltree::Node) -> Result<FormData, String> {
+    node.children()
+        .find(|node| node.has_tag_name("formData"))
+        .ok_or("formData not found".to_string())
+        .and_then(|form_data_node| {
+            let general_info = parse_general_info(&form_data_node)?;
+            let fund_info = parse_fund_info(&form_data_node)?;
+            let investments = parse_investments(&form_data_node)?;
+            let signature = parse_signature(&form_data_node)?;
+
+            Ok(FormData {
+                general_info,
+                fund_info,
+                investments,
+                signature,
+            })
+        })
+}
+
+fn parse_general_info(node: &roxmltree::Node) -> Result<GeneralInfo, String> {
+    node.children()
+        .find(|node| node.has_tag_name("genInfo"))
+        .ok_or("genInfo not found".to_string())
+        .and_then(|general_info_node| {
+            let registrant_name = get_string(&general_info_node, "regName")?;
+            let registrant_file_number = get_string(&general_info_node, "regFileNumber").ok();
+            let registrant_cik = get_string(&general_info_node, "regCik").ok();
+            let registrant_lei = get_string(&general_info_node, "regLei").ok();
+            let registrant_address = parse_registrant_address(&general_info_node);
+            let registrant_phone = get_string(&general_info_node, "regPhone").ok();
+            let series_name = get_string(&general_info_node, "seriesName").ok();
+            let series_id = get_string(&general_info_node, "seriesId").ok();
+            let series_lei = get_string(&general_info_node, "seriesLei").ok();
+            let report_period_end = get_string(&general_info_node, "repPdEnd")?;
+            let report_period_date = get_string(&general_info_node, "repPdDate")?;
+            let is_final_filing = get_bool(&general_info_node, "isFinalFiling").ok();
+
+            Ok(GeneralInfo {
+                registrant_name,
+                registrant_file_number,
+                registrant_cik,
+                registrant_lei,
+                registrant_address,
+                registrant_phone,
+                series_name,
+                series_id,
+                series_lei,
+                report_period_end,
+                report_period_date,
+                is_final_filing,
+            })
+        })
+}
+
+fn parse_registrant_address(node: &roxmltree::Node) -> Address {
+    let state_node = node
+        .children()
+        .find(|node| node.has_tag_name("regStateConditional"));
+
+    Address {
+        street1: get_string(node, "regStreet1").ok(),
+        street2: get_string(node, "regStreet2").ok(),
+        city: get_string(node, "regCity").ok(),
+        state: state_node
+            .and_then(|node| node.attribute("regState"))
+            .map(|s| s.to_string()),
+        country: state_node
+            .and_then(|node| node.attribute("regCountry"))
+            .map(|s| s.to_string()),
+        zip_code: get_string(node, "regZipOrPostalCode").ok(),
+    }
+}
+
+fn parse_fund_info(node: &roxmltree::Node) -> Result<FundInfo, String> {
+    node.children()
+        .find(|node| node.has_tag_name("fundInfo"))
+        .ok_or("fundInfo not found".to_string())
+        .and_then(|fund_info_node| {
+            let total_assets = get_float64(&fund_info_node, "totAssets")?;
+            let total_liabilities = get_float64(&fund_info_node, "totLiabs")?;
+            let net_assets = get_float64(&fund_info_node, "netAssets")?;
+            let assets_attributable_to_misc_securities =
+                get_optional_float64(&fund_info_node, "assetsAttrMiscSec")?;
+            let assets_invested = get_optional_float64(&fund_info_node, "assetsInvested")?;
+            let borrowings = parse_borrowings(&fund_info_node)?;
+            let delayed_delivery = get_optional_float64(&fund_info_node, "delayDeliv")?;
+            let standby_commitment = get_optional_float64(&fund_info_node, "standByCommit")?;
+            let liquidation_preference = get_optional_float64(&fund_info_node, "liquidPref")?;
+            let cash_not_reported = get_optional_float64(&fund_info_node, "cshNotRptdInCorD")?;
+            let is_non_cash_collateral = get_bool(&fund_info_node, "isNonCashCollateral").ok();
+            let return_info = parse_return_info(&fund_info_node)?;
+            let flows = parse_flows(&fund_info_node)?;
+
+            Ok(FundInfo {
+                total_assets,
+                total_liabilities,
+                net_assets,
+                assets_attributable_to_misc_securities,
+                assets_invested,
+                borrowings,
+                delayed_delivery,
+                standby_commitment,
+                liquidation_preference,
+                cash_not_reported,
+                is_non_cash_collateral,
+                return_info,
+                flows,
+            })
+        })
+}
+
+fn parse_borrowings(node: &roxmltree::Node) -> Result<Borrowings, String> {
+    Ok(Borrowings {
+        one_year_banks: get_optional_float64(node, "amtPayOneYrBanksBorr")?,
+        one_year_controlled_companies: get_optional_float64(node, "amtPayOneYrCtrldComp")?,
+        one_year_other_affiliates: get_optional_float64(node, "amtPayOneYrOthAffil")?,
+        one_year_other: get_optional_float64(node, "amtPayOneYrOther")?,
+        after_one_year_banks: get_optional_float64(node, "amtPayAftOneYrBanksBorr")?,
+        after_one_year_controlled_companies: get_optional_float64(node, "amtPayAftOneYrCtrldComp")?,
+        after_one_year_other_affiliates: get_optional_float64(node, "amtPayAftOneYrOthAffil")?,
+        after_one_year_other: get_optional_float64(node, "amtPayAftOneYrOther")?,
+    })
+}
+
+fn parse_return_info(node: &roxmltree::Node) -> Result<Option<ReturnInfo>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("returnInfo"))
+        .map(|return_info_node| {
+            let monthly_total_returns = return_info_node
+                .children()
+                .filter(|node| node.has_tag_name("monthlyTotReturns"))
+                .flat_map(|node| node.children())
+                .filter(|node| node.has_tag_name("monthlyTotReturn"))
+                .map(|return_node| {
+                    let class_id = return_node.attribute("classId").map(|s| s.to_string());
+                    let month1 = get_float_attribute(&return_node, "rtn1")?;
+                    let month2 = get_float_attribute(&return_node, "rtn2")?;
+                    let month3 = get_float_attribute(&return_node, "rtn3")?;
+
+                    Ok(MonthlyTotalReturn {
+                        class_id,
+                        month1,
+                        month2,
+                        month3,
+                    })
+                })
+                .collect::<Result<Vec<MonthlyTotalReturn>, String>>()?;
+
+            let other_monthly_gains = ["othMon1", "othMon2", "othMon3"]
+                .iter()
+                .zip(1..)
+                .filter_map(|(tag, month)| {
+                    return_info_node
+                        .children()
+                        .find(|node| node.has_tag_name(*tag))
+                        .map(|gain_node| {
+                            let net_realized_gain =
+                                get_float_attribute(&gain_node, "netRealizedGain")?;
+                            let net_unrealized_appreciation =
+                                get_float_attribute(&gain_node, "netUnrealizedAppr")?;
+
+                            Ok(MonthlyGain {
+                                month,
+                                net_realized_gain,
+                                net_unrealized_appreciation,
+                            })
+                        })
+                })
+                .collect::<Result<Vec<MonthlyGain>, String>>()?;
+
+            Ok(ReturnInfo {
+                monthly_total_returns,
+                other_monthly_gains,
+            })
+        })
+        .transpose()
+}
+
+fn parse_flows(node: &roxmltree::Node) -> Result<Vec<Flow>, String> {
+    ["mon1Flow", "mon2Flow", "mon3Flow"]
+        .iter()
+        .zip(1..)
+        .filter_map(|(tag, month)| {
+            node.children()
+                .find(|node| node.has_tag_name(*tag))
+                .map(|flow_node| {
+                    let sales = get_float_attribute(&flow_node, "sales")?;
+                    let reinvestment = get_float_attribute(&flow_node, "reinvestment")?;
+                    let redemption = get_float_attribute(&flow_node, "redemption")?;
+
+                    Ok(Flow {
+                        month,
+                        sales,
+                        reinvestment,
+                        redemption,
+                    })
+                })
+        })
+        .collect()
+}
+
+fn parse_investments(node: &roxmltree::Node) -> Result<Vec<Investment>, String> {
+    node.children()
+        .filter(|node| node.has_tag_name("invstOrSecs"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("invstOrSec"))
+        .enumerate()
+        .map(|(index, investment_node)| {
+            parse_investment(&investment_node)
+                .map_err(|e| format!("invstOrSec {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+fn parse_investment(node: &roxmltree::Node) -> Result<Investment, String> {
+    let name = get_string(node, "name").ok();
+    let lei = get_string(node, "lei").ok();
+    let title = get_string(node, "title").ok();
+    let cusip = get_string(node, "cusip").ok();
+    let identifiers = parse_identifiers(node).unwrap_or(Identifiers {
+        isin: None,
+        ticker: None,
+        other: vec![],
+    });
+    let balance = get_optional_float64(node, "balance")?;
+    let units = get_string(node, "units").ok();
+    let other_units_description = get_string(node, "descOthUnits").ok();
+    let currency_node = node
+        .children()
+        .find(|node| node.has_tag_name("currencyConditional"));
+    let currency_code = get_string(node, "curCd").ok().or_else(|| {
+        currency_node
+            .and_then(|node| node.attribute("curCd"))
+            .map(|s| s.to_string())
+    });
+    let exchange_rate = currency_node
+        .map(|node| get_float_attribute(&node, "exchangeRt"))
+        .transpose()?
+        .flatten();
+    let value_usd = get_optional_float64(node, "valUSD")?;
+    let percent_value = get_optional_float64(node, "pctVal")?;
+    let payoff_profile = get_string(node, "payoffProfile").ok();
+    let (asset_category, asset_category_description) =
+        get_conditional(node, "assetCat", "assetConditional");
+    let (issuer_category, issuer_category_description) =
+        get_conditional(node, "issuerCat", "issuerConditional");
+    let investment_country = get_string(node, "invCountry").ok();
+    let is_restricted_security = get_bool(node, "isRestrictedSec").ok();
+    let fair_value_level = get_string(node, "fairValLevel").ok();
+    let debt_security = parse_debt_security(node)?;
+    let derivative = parse_derivative(node)?;
+    let security_lending = parse_security_lending(node)?;
+
+    Ok(Investment {
+        name,
+        lei,
+        title,
+        cusip,
+        identifiers,
+        balance,
+        units,
+        other_units_description,
+        currency_code,
+        exchange_rate,
+        value_usd,
+        percent_value,
+        payoff_profile,
+        asset_category,
+        asset_category_description,
+        issuer_category,
+        issuer_category_description,
+        investment_country,
+        is_restricted_security,
+        fair_value_level,
+        debt_security,
+        derivative,
+        security_lending,
+    })
+}
+
+fn parse_identifiers(node: &roxmltree::Node) -> Option<Identifiers> {
+    node.children()
+        .find(|node| node.has_tag_name("identifiers"))
+        .map(|identifiers_node| {
+            let isin = get_value_attribute(&identifiers_node, "isin");
+            let ticker = get_value_attribute(&identifiers_node, "ticker");
+            let other = identifiers_node
+                .children()
+                .filter(|node| node.has_tag_name("other"))
+                .map(|other_node| OtherIdentifier {
+                    description: other_node.attribute("otherDesc").map(|s| s.to_string()),
+                    value: other_node.attribute("value").map(|s| s.to_string()),
+                })
+                .collect();
+
+            Identifiers {
+                isin,
+                ticker,
+                other,
+            }
+        })
+}
+
+fn parse_debt_security(node: &roxmltree::Node) -> Result<Option<DebtSecurity>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("debtSec"))
+        .map(|debt_node| {
+            let maturity_date = get_string(&debt_node, "maturityDt").ok();
+            let coupon_kind = get_string(&debt_node, "couponKind").ok();
+            let annualized_rate = get_optional_float64(&debt_node, "annualizedRt")?;
+            let is_default = get_bool(&debt_node, "isDefault").ok();
+            let are_interest_payments_in_arrears =
+                get_bool(&debt_node, "areIntrstPmntsInArrs").ok();
+            let is_paid_in_kind = get_bool(&debt_node, "isPaidKind").ok();
+            let is_mandatory_convertible = get_bool(&debt_node, "isMandatoryConvrtbl").ok();
+            let is_contingent_convertible = get_bool(&debt_node, "isContngtConvrtbl").ok();
+
+            Ok(DebtSecurity {
+                maturity_date,
+                coupon_kind,
+                annualized_rate,
+                is_default,
+                are_interest_payments_in_arrears,
+                is_paid_in_kind,
+                is_mandatory_convertible,
+                is_contingent_convertible,
+            })
+        })
+        .transpose()
+}
+
+fn parse_derivative(node: &roxmltree::Node) -> Result<Option<Derivative>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("derivativeInfo"))
+        .and_then(|derivative_info_node| {
+            derivative_info_node
+                .children()
+                .find(|node| DERIVATIVE_TYPES.contains(&node.tag_name().name()))
+        })
+        .map(|derivative_node| {
+            let derivative_type = derivative_node.tag_name().name().to_string();
+            let derivative_category = derivative_node.attribute("derivCat").map(|s| s.to_string());
+            let counterparties = derivative_node
+                .children()
+                .filter(|node| node.has_tag_name("counterparties"))
+                .map(|counterparty_node| Counterparty {
+                    name: get_string(&counterparty_node, "counterpartyName").ok(),
+                    lei: get_string(&counterparty_node, "counterpartyLei").ok(),
+                })
+                .collect();
+            let payoff_profile = get_string(&derivative_node, "payOffProf").ok();
+            let reference_instrument = parse_reference_instrument(&derivative_node);
+            let put_or_call = get_string(&derivative_node, "putOrCall").ok();
+            let written_or_purchased = get_string(&derivative_node, "writtenOrPur").ok();
+            let share_number = get_optional_float64(&derivative_node, "shareNo")?;
+            let exercise_price = get_optional_float64(&derivative_node, "exercisePrice")?;
+            let exercise_price_currency_code =
+                get_string(&derivative_node, "exercisePriceCurCd").ok();
+            let expiration_date = get_string(&derivative_node, "expDt")
+                .or_else(|_| get_string(&derivative_node, "expDate"))
+                .ok();
+            let termination_date = get_string(&derivative_node, "terminationDt").ok();
+            let settlement_date = get_string(&derivative_node, "settlementDt").ok();
+            let notional_amount = get_optional_float64(&derivative_node, "notionalAmt")?;
+            let currency_code = get_string(&derivative_node, "curCd").ok();
+            let amount_currency_sold = get_optional_float64(&derivative_node, "amtCurSold")?;
+            let currency_sold = get_string(&derivative_node, "curSold").ok();
+            let amount_currency_purchased = get_optional_float64(&derivative_node, "amtCurPur")?;
+            let currency_purchased = get_string(&derivative_node, "curPur").ok();
+            let delta = get_optional_float64(&derivative_node, "delta")?;
+            let unrealized_appreciation = get_optional_float64(&derivative_node, "unrealizedAppr")?;
+
+            Ok(Derivative {
+                derivative_type,
+                derivative_category,
+                counterparties,
+                payoff_profile,
+                reference_instrument,
+                put_or_call,
+                written_or_purchased,
+                share_number,
+                exercise_price,
+                exercise_price_currency_code,
+                expiration_date,
+                termination_date,
+                settlement_date,
+                notional_amount,
+                currency_code,
+                amount_currency_sold,
+                currency_sold,
+                amount_currency_purchased,
+                currency_purchased,
+                delta,
+                unrealized_appreciation,
+            })
+        })
+        .transpose()
+}
+
+fn parse_reference_instrument(node: &roxmltree::Node) -> Option<ReferenceInstrument> {
+    node.children()
+        .find(|node| node.has_tag_name("descRefInstrmnt"))
+        .and_then(|reference_node| reference_node.first_element_child())
+        .map(|instrument_node| ReferenceInstrument {
+            name: get_string(&instrument_node, "issuerName")
+                .or_else(|_| get_string(&instrument_node, "indexName"))
+                .or_else(|_| get_string(&instrument_node, "name"))
+                .ok(),
+            title: get_string(&instrument_node, "issueTitle")
+                .or_else(|_| get_string(&instrument_node, "title"))
+                .ok(),
+            identifiers: parse_identifiers(&instrument_node),
+        })
+}
+
+fn parse_security_lending(node: &roxmltree::Node) -> Result<Option<SecurityLending>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("securityLending"))
+        .map(|lending_node| {
+            let is_cash_collateral = get_bool(&lending_node, "isCashCollateral").ok();
+            let is_non_cash_collateral = get_bool(&lending_node, "isNonCashCollateral").ok();
+            let is_loan_by_fund = get_bool(&lending_node, "isLoanByFund").ok();
+
+            Ok(SecurityLending {
+                is_cash_collateral,
+                is_non_cash_collateral,
+                is_loan_by_fund,
+            })
+        })
+        .transpose()
+}
+
+fn parse_signature(node: &roxmltree::Node) -> Result<Option<Signature>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("signature"))
+        .map(|signature_node| {
+            let date_signed = get_string(&signature_node, "dateSigned").ok();
+            let name_of_applicant = get_string(&signature_node, "nameOfApplicant").ok();
+            let signature = get_string(&signature_node, "signature").ok();
+            let signer_name = get_string(&signature_node, "signerName").ok();
+            let title = get_string(&signature_node, "title").ok();
+
+            Ok(Signature {
+                date_signed,
+                name_of_applicant,
+                signature,
+                signer_name,
+                title,
+            })
+        })
+        .transpose()
+}
+
+fn get_conditional(
+    node: &roxmltree::Node,
+    tag: &str,
+    conditional_tag: &str,
+) -> (Option<String>, Option<String>) {
+    match get_string(node, tag) {
+        Ok(value) => (Some(value), None),
+        Err(_) => node
+            .children()
+            .find(|node| node.has_tag_name(conditional_tag))
+            .map(|conditional_node| {
+                (
+                    conditional_node.attribute(tag).map(|s| s.to_string()),
+                    conditional_node.attribute("desc").map(|s| s.to_string()),
+                )
+            })
+            .unwrap_or((None, None)),
+    }
+}
+
+fn get_value_attribute(node: &roxmltree::Node, tag: &str) -> Option<String> {
+    node.children()
+        .find(|node| node.has_tag_name(tag))
+        .and_then(|node| node.attribute("value"))
+        .map(|s| s.to_string())
+}
+
+fn get_float_attribute(node: &roxmltree::Node, attribute: &str) -> Result<Option<f64>, String> {
+    match node.attribute(attribute) {
+        Some(value) if value != "N/A" && !value.is_empty() => {
+            parse_float64(value, attribute).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
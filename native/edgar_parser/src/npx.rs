@@ -0,0 +1,519 @@
+use crate::xml::{get_bool, get_int32, get_ints, get_optional_float64, get_string};
+use roxmltree::Document as XMLDoc;
+use rustler::NifMap;
+
+// https://www.sec.gov/info/edgar/specifications/formnpxxmltechspec
+
+#[derive(NifMap)]
+pub struct Document {
+    schema_version: Option<String>,
+    header_data: HeaderData,
+    form_data: FormData,
+}
+
+#[derive(NifMap)]
+pub struct HeaderData {
+    submission_type: String,
+    filer_info: FilerInfo,
+}
+
+#[derive(NifMap)]
+pub struct FilerInfo {
+    live_test_flag: Option<String>,
+    credentials: Credentials,
+    investment_company_type: Option<String>,
+    period_of_report: String,
+    series_classes: Vec<SeriesClass>,
+}
+
+#[derive(NifMap)]
+pub struct Credentials {
+    cik: String,
+    ccc: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SeriesClass {
+    series_id: String,
+    class_ids: Vec<String>,
+}
+
+#[derive(NifMap)]
+pub struct FormData {
+    cover_page: CoverPage,
+    summary_page: Option<SummaryPage>,
+    series: Vec<Series>,
+    signature_block: Option<SignatureBlock>,
+}
+
+#[derive(NifMap)]
+pub struct CoverPage {
+    year_or_quarter: Option<String>,
+    report_calendar_year: Option<String>,
+    report_quarter_year: Option<String>,
+    is_amendment: Option<bool>,
+    amendment_no: Option<String>,
+    reporting_person: ReportingPerson,
+    agent_for_service: Option<AgentForService>,
+    report_type: Option<String>,
+    confidential_treatment: Option<bool>,
+    file_number: Option<String>,
+    crd_number: Option<String>,
+    sec_file_number: Option<String>,
+    lei_number: Option<String>,
+    explanatory_information: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct ReportingPerson {
+    name: String,
+    phone_number: Option<String>,
+    address: Option<Address>,
+}
+
+#[derive(NifMap)]
+pub struct AgentForService {
+    name: Option<String>,
+    address: Option<Address>,
+}
+
+#[derive(NifMap)]
+pub struct Address {
+    street1: Option<String>,
+    street2: Option<String>,
+    city: Option<String>,
+    state_or_country: Option<String>,
+    zip_code: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SummaryPage {
+    other_included_managers_count: Option<i32>,
+    other_managers: Vec<OtherManager>,
+}
+
+#[derive(NifMap)]
+pub struct OtherManager {
+    serial_no: Option<i64>,
+    form_13f_file_number: Option<String>,
+    sec_file_number: Option<String>,
+    crd_number: Option<String>,
+    lei_number: Option<String>,
+    name: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct Series {
+    id: Option<String>,
+    name: Option<String>,
+    lei: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SignatureBlock {
+    reporting_person: Option<String>,
+    signature: Option<String>,
+    printed_signature: Option<String>,
+    title: Option<String>,
+    date: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct Table {
+    entries: Vec<TableEntry>,
+}
+
+#[derive(NifMap)]
+pub struct TableEntry {
+    issuer_name: String,
+    cusip: Option<String>,
+    isin: Option<String>,
+    figi: Option<String>,
+    meeting_date: String,
+    vote_description: String,
+    vote_categories: Vec<String>,
+    vote_source: Option<String>,
+    shares_voted: Option<f64>,
+    shares_on_loan: Option<f64>,
+    votes: Vec<VoteRecord>,
+    other_managers: Vec<i64>,
+    vote_series: Option<String>,
+    vote_other_info: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct VoteRecord {
+    how_voted: String,
+    shares_voted: Option<f64>,
+    management_recommendation: Option<String>,
+}
+
+#[rustler::nif]
+pub fn parse_npx_document(xml: &str) -> Result<Document, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+    let schema_version = get_string(&root_node, "schemaVersion").ok();
+    let header_data = parse_header_data(&root_node)?;
+    let form_data = parse_form_data(&root_node)?;
+
+    Ok(Document {
+        schema_version,
+        header_data,
+        form_data,
+    })
+}
+
+fn parse_header_data(node: &roxmltree::Node) -> Result<HeaderData, String> {
+    node.children()
+        .find(|node| node.has_tag_name("headerData"))
+        .ok_or("headerData not found".to_string())
+        .and_then(|header_data_node| {
+            let submission_type = get_string(&header_data_node, "submissionType")?;
+            let filer_info = parse_filer_info(&header_data_node)?;
+
+            Ok(HeaderData {
+                submission_type,
+                filer_info,
+            })
+        })
+}
+
+fn parse_filer_info(node: &roxmltree::Node) -> Result<FilerInfo, String> {
+    node.children()
+        .find(|node| node.has_tag_name("filerInfo"))
+        .ok_or("filerInfo not found".to_string())
+        .and_then(|filer_info_node| {
+            let live_test_flag = get_string(&filer_info_node, "liveTestFlag").ok();
+            let credentials = parse_credentials(&filer_info_node)?;
+            let investment_company_type =
+                get_string(&filer_info_node, "investmentCompanyType").ok();
+            let period_of_report = get_string(&filer_info_node, "periodOfReport")?;
+            let series_classes = parse_series_classes(&filer_info_node)?;
+
+            Ok(FilerInfo {
+                live_test_flag,
+                credentials,
+                investment_company_type,
+                period_of_report,
+                series_classes,
+            })
+        })
+}
+
+fn parse_credentials(node: &roxmltree::Node) -> Result<Credentials, String> {
+    node.descendants()
+        .find(|node| {
+            node.has_tag_name("issuerCredentials") || node.has_tag_name("filerCredentials")
+        })
+        .ok_or("issuerCredentials not found".to_string())
+        .and_then(|credentials_node| {
+            let cik = get_string(&credentials_node, "cik")?;
+            let ccc = get_string(&credentials_node, "ccc").ok();
+
+            Ok(Credentials { cik, ccc })
+        })
+}
+
+fn parse_series_classes(node: &roxmltree::Node) -> Result<Vec<SeriesClass>, String> {
+    node.descendants()
+        .filter(|node| node.has_tag_name("rptSeriesClassInfo"))
+        .map(|series_node| {
+            let series_id = get_string(&series_node, "seriesId")?;
+            let class_ids = series_node
+                .children()
+                .filter(|node| node.has_tag_name("classInfo"))
+                .filter_map(|node| get_string(&node, "classId").ok())
+                .collect();
+
+            Ok(SeriesClass {
+                series_id,
+                class_ids,
+            })
+        })
+        .collect()
+}
+
+fn parse_form_data(node: &roxmltree::Node) -> Result<FormData, String> {
+    node.children()
+        .find(|node| node.has_tag_name("formData"))
+        .ok_or("formData not found".to_string())
+        .and_then(|form_data_node| {
+            let cover_page = parse_cover_page(&form_data_node)?;
+            let summary_page = parse_summary_page(&form_data_node)?;
+            let series = parse_series(&form_data_node)?;
+            let signature_block = parse_signature_block(&form_data_node)?;
+
+            Ok(FormData {
+                cover_page,
+                summary_page,
+                series,
+                signature_block,
+            })
+        })
+}
+
+fn parse_cover_page(node: &roxmltree::Node) -> Result<CoverPage, String> {
+    node.children()
+        .find(|node| node.has_tag_name("coverPage"))
+        .ok_or("coverPage not found".to_string())
+        .and_then(|cover_page_node| {
+            let year_or_quarter = get_string(&cover_page_node, "yearOrQuarter").ok();
+            let report_calendar_year = get_string(&cover_page_node, "reportCalendarYear").ok();
+            let report_quarter_year = get_string(&cover_page_node, "reportQuarterYear").ok();
+            let amendment_info_node = cover_page_node
+                .children()
+                .find(|node| node.has_tag_name("amendmentInfo"));
+            let is_amendment =
+                amendment_info_node.and_then(|node| get_bool(&node, "isAmendment").ok());
+            let amendment_no =
+                amendment_info_node.and_then(|node| get_string(&node, "amendmentNo").ok());
+            let reporting_person = parse_reporting_person(&cover_page_node)?;
+            let agent_for_service = parse_agent_for_service(&cover_page_node)?;
+            let report_info_node = cover_page_node
+                .children()
+                .find(|node| node.has_tag_name("reportInfo"));
+            let report_type =
+                report_info_node.and_then(|node| get_string(&node, "reportType").ok());
+            let confidential_treatment =
+                report_info_node.and_then(|node| get_bool(&node, "confidentialTreatment").ok());
+            let file_number = get_string(&cover_page_node, "fileNumber").ok();
+            let crd_number = get_string(&cover_page_node, "reportingCrdNumber").ok();
+            let sec_file_number = get_string(&cover_page_node, "reportingSecFileNumber").ok();
+            let lei_number = get_string(&cover_page_node, "leiNumber").ok();
+            let explanatory_information = cover_page_node
+                .children()
+                .find(|node| node.has_tag_name("explanatoryInformation"))
+                .and_then(|node| {
+                    get_string(&node, "explanatoryNotes")
+                        .ok()
+                        .or_else(|| node.text().map(|s| s.trim().to_string()))
+                })
+                .filter(|s| !s.is_empty());
+
+            Ok(CoverPage {
+                year_or_quarter,
+                report_calendar_year,
+                report_quarter_year,
+                is_amendment,
+                amendment_no,
+                reporting_person,
+                agent_for_service,
+                report_type,
+                confidential_treatment,
+                file_number,
+                crd_number,
+                sec_file_number,
+                lei_number,
+                explanatory_information,
+            })
+        })
+}
+
+fn parse_reporting_person(node: &roxmltree::Node) -> Result<ReportingPerson, String> {
+    node.children()
+        .find(|node| node.has_tag_name("reportingPerson"))
+        .ok_or("reportingPerson not found".to_string())
+        .and_then(|person_node| {
+            let name = get_string(&person_node, "name")?;
+            let phone_number = get_string(&person_node, "phoneNumber").ok();
+            let address = parse_address(&person_node)?;
+
+            Ok(ReportingPerson {
+                name,
+                phone_number,
+                address,
+            })
+        })
+}
+
+fn parse_agent_for_service(node: &roxmltree::Node) -> Result<Option<AgentForService>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("agentForService"))
+        .map(|agent_node| {
+            let name = get_string(&agent_node, "name").ok();
+            let address = parse_address(&agent_node)?;
+
+            Ok(AgentForService { name, address })
+        })
+        .transpose()
+}
+
+fn parse_address(node: &roxmltree::Node) -> Result<Option<Address>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("address"))
+        .map(|address_node| {
+            let street1 = get_string(&address_node, "street1").ok();
+            let street2 = get_string(&address_node, "street2").ok();
+            let city = get_string(&address_node, "city").ok();
+            let state_or_country = get_string(&address_node, "stateOrCountry").ok();
+            let zip_code = get_string(&address_node, "zipCode").ok();
+
+            Ok(Address {
+                street1,
+                street2,
+                city,
+                state_or_country,
+                zip_code,
+            })
+        })
+        .transpose()
+}
+
+fn parse_summary_page(node: &roxmltree::Node) -> Result<Option<SummaryPage>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("summaryPage"))
+        .map(|summary_page_node| {
+            let other_included_managers_count =
+                get_int32(&summary_page_node, "otherIncludedManagersCount").ok();
+            let other_managers = summary_page_node
+                .descendants()
+                .filter(|node| node.has_tag_name("investmentManagers"))
+                .map(|manager_node| parse_other_manager(&manager_node))
+                .collect::<Result<Vec<OtherManager>, String>>()?;
+
+            Ok(SummaryPage {
+                other_included_managers_count,
+                other_managers,
+            })
+        })
+        .transpose()
+}
+
+fn parse_other_manager(node: &roxmltree::Node) -> Result<OtherManager, String> {
+    let serial_no = get_ints(node, "serialNo")?.first().copied();
+    let form_13f_file_number = get_string(node, "form13FFileNumber").ok();
+    let sec_file_number = get_string(node, "secFileNumber").ok();
+    let crd_number = get_string(node, "crdNumber").ok();
+    let lei_number = get_string(node, "leiNumber").ok();
+    let name = get_string(node, "name").ok();
+
+    Ok(OtherManager {
+        serial_no,
+        form_13f_file_number,
+        sec_file_number,
+        crd_number,
+        lei_number,
+        name,
+    })
+}
+
+fn parse_series(node: &roxmltree::Node) -> Result<Vec<Series>, String> {
+    let series = node
+        .children()
+        .filter(|node| node.has_tag_name("seriesPage"))
+        .flat_map(|node| node.descendants())
+        .filter(|node| node.has_tag_name("seriesReports"))
+        .map(|series_node| Series {
+            id: get_string(&series_node, "idOfSeries").ok(),
+            name: get_string(&series_node, "nameOfSeries").ok(),
+            lei: get_string(&series_node, "leiOfSeries").ok(),
+        })
+        .collect();
+    Ok(series)
+}
+
+fn parse_signature_block(node: &roxmltree::Node) -> Result<Option<SignatureBlock>, String> {
+    node.children()
+        .find(|node| node.has_tag_name("signaturePage"))
+        .map(|signature_node| {
+            let reporting_person = get_string(&signature_node, "reportingPerson").ok();
+            let signature = get_string(&signature_node, "txSignature").ok();
+            let printed_signature = get_string(&signature_node, "txPrintedSignature").ok();
+            let title = get_string(&signature_node, "txTitle").ok();
+            let date = get_string(&signature_node, "txAsOfDate").ok();
+
+            Ok(SignatureBlock {
+                reporting_person,
+                signature,
+                printed_signature,
+                title,
+                date,
+            })
+        })
+        .transpose()
+}
+
+#[rustler::nif]
+pub fn parse_npx_table(xml: &str) -> Result<Table, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+
+    let entries = root_node
+        .children()
+        .filter(|node| node.has_tag_name("proxyTable"))
+        .enumerate()
+        .map(|(index, proxy_node)| {
+            parse_table_entry(&proxy_node).map_err(|e| format!("proxyTable {}: {}", index + 1, e))
+        })
+        .collect::<Result<Vec<TableEntry>, String>>()?;
+
+    Ok(Table { entries })
+}
+
+fn parse_table_entry(node: &roxmltree::Node) -> Result<TableEntry, String> {
+    let issuer_name = get_string(node, "issuerName")?;
+    let cusip = get_string(node, "cusip").ok();
+    let isin = get_string(node, "isin").ok();
+    let figi = get_string(node, "figi").ok();
+    let meeting_date = get_string(node, "meetingDate")?;
+    let vote_description = get_string(node, "voteDescription")?;
+    let vote_categories = node
+        .children()
+        .filter(|node| node.has_tag_name("voteCategories"))
+        .flat_map(|node| node.descendants())
+        .filter(|node| node.has_tag_name("categoryType"))
+        .filter_map(|node| node.text())
+        .map(|s| s.trim().to_string())
+        .collect();
+    let vote_source = get_string(node, "voteSource").ok();
+    let shares_voted = get_optional_float64(node, "sharesVoted")?;
+    let shares_on_loan = get_optional_float64(node, "sharesOnLoan")?;
+    let votes = parse_vote_records(node)?;
+    let other_managers = node
+        .children()
+        .filter(|node| node.has_tag_name("voteManager"))
+        .flat_map(|node| node.descendants())
+        .filter(|node| node.has_tag_name("otherManagers"))
+        .map(|node| get_ints(&node, "otherManager"))
+        .collect::<Result<Vec<Vec<i64>>, String>>()?
+        .concat();
+    let vote_series = get_string(node, "voteSeries").ok();
+    let vote_other_info = get_string(node, "voteOtherInfo").ok();
+
+    Ok(TableEntry {
+        issuer_name,
+        cusip,
+        isin,
+        figi,
+        meeting_date,
+        vote_description,
+        vote_categories,
+        vote_source,
+        shares_voted,
+        shares_on_loan,
+        votes,
+        other_managers,
+        vote_series,
+        vote_other_info,
+    })
+}
+
+fn parse_vote_records(node: &roxmltree::Node) -> Result<Vec<VoteRecord>, String> {
+    node.children()
+        .filter(|node| node.has_tag_name("vote"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("voteRecord"))
+        .map(|record_node| {
+            let how_voted = get_string(&record_node, "howVoted")?;
+            let shares_voted = get_optional_float64(&record_node, "sharesVoted")?;
+            let management_recommendation =
+                get_string(&record_node, "managementRecommendation").ok();
+
+            Ok(VoteRecord {
+                how_voted,
+                shares_voted,
+                management_recommendation,
+            })
+        })
+        .collect()
+}
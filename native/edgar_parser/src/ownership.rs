@@ -1,51 +1,54 @@
 use crate::xml::{get_bool, get_string, parse_value, Value};
 use roxmltree::Document as XMLDoc;
 use rustler::NifMap;
+use std::collections::{HashMap, HashSet};
 
 // https://www.sec.gov/info/edgar/specifications/ownershipxmltechspec
 
 #[derive(NifMap)]
 pub struct Document {
     schema_version: Option<String>,
-    document_type: String,
-    period_of_report: String,
+    pub(crate) document_type: String,
+    pub(crate) period_of_report: Option<String>,
     date_of_original_submission: Option<String>,
     no_securities_owned: Option<bool>,
     not_subject_to_section_16: Option<bool>,
     form3_holdings_reported: Option<bool>,
     form4_transactions_reported: Option<bool>,
-    issuer: Issuer,
-    reporting_owner: ReportingOwner,
-    aff10b5_one: Option<bool>,
-    non_derivative_table: Option<NonDerivativeTable>,
-    derivative_table: Option<DerivativeTable>,
-    footnotes: Vec<Footnote>,
+    pub(crate) issuer: Issuer,
+    pub(crate) reporting_owner: ReportingOwner,
+    reporting_owners: Vec<ReportingOwner>,
+    pub(crate) aff10b5_one: Option<bool>,
+    pub(crate) non_derivative_table: Option<NonDerivativeTable>,
+    pub(crate) derivative_table: Option<DerivativeTable>,
+    pub(crate) footnotes: Vec<Footnote>,
     remarks: Option<String>,
-    owner_signature: OwnerSignature,
+    owner_signatures: Vec<OwnerSignature>,
+    warnings: Vec<String>,
 }
 
 #[derive(NifMap)]
 pub struct Issuer {
-    cik: String,
+    pub(crate) cik: String,
     name: Option<String>,
-    trading_symbol: String,
+    pub(crate) trading_symbol: Option<String>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct ReportingOwner {
-    id: ReportingOwnerID,
+    pub(crate) id: ReportingOwnerID,
     address: Option<ReportingOwnerAddress>,
     relationship: Option<ReportingOwnerRelationship>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct ReportingOwnerID {
-    cik: String,
+    pub(crate) cik: String,
     ccc: Option<String>,
-    name: Option<String>,
+    pub(crate) name: Option<String>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct ReportingOwnerAddress {
     street1: Option<String>,
     street2: Option<String>,
@@ -53,9 +56,10 @@ pub struct ReportingOwnerAddress {
     state: Option<String>,
     zip_code: Option<String>,
     state_description: Option<String>,
+    good_address: Option<bool>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct ReportingOwnerRelationship {
     is_director: Option<bool>,
     is_officer: Option<bool>,
@@ -67,41 +71,42 @@ pub struct ReportingOwnerRelationship {
 
 #[derive(NifMap)]
 pub struct NonDerivativeTable {
-    transactions: Vec<NonDerivativeTransaction>,
+    pub(crate) transactions: Vec<NonDerivativeTransaction>,
     holdings: Vec<NonDerivativeHolding>,
 }
 
 #[derive(NifMap)]
 pub struct DerivativeTable {
-    transactions: Vec<DerivativeTransaction>,
+    pub(crate) transactions: Vec<DerivativeTransaction>,
     holdings: Vec<DerivativeHolding>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct NonDerivativeTransaction {
-    security_title: Option<ValueFootnote>,
-    transaction_date: Option<ValueFootnote>,
-    deemed_execution_date: Option<ValueFootnote>,
-    transaction_coding: Option<TransactionCoding>,
-    transaction_timeliness: Option<ValueFootnote>,
-    transaction_amounts: Option<TransactionAmounts>,
-    post_transaction_amounts: Option<PostTransactionAmounts>,
-    ownership_nature: Option<OwnershipNature>,
+    pub(crate) security_title: Option<ValueFootnote>,
+    pub(crate) transaction_date: Option<ValueFootnote>,
+    pub(crate) deemed_execution_date: Option<ValueFootnote>,
+    pub(crate) transaction_coding: Option<TransactionCoding>,
+    pub(crate) transaction_timeliness: Option<ValueFootnote>,
+    pub(crate) transaction_amounts: Option<TransactionAmounts>,
+    pub(crate) post_transaction_amounts: Option<PostTransactionAmounts>,
+    pub(crate) ownership_nature: Option<OwnershipNature>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct DerivativeTransaction {
-    security_title: Option<ValueFootnote>,
-    conversion_or_exercise_price: Option<ValueFootnote>,
-    deemed_execution_date: Option<ValueFootnote>,
-    transaction_coding: Option<TransactionCoding>,
-    transaction_timeliness: Option<ValueFootnote>,
-    transaction_amounts: Option<DerivativeTransactionAmounts>,
-    exercise_date: Option<ValueFootnote>,
-    expiration_date: Option<ValueFootnote>,
-    underlying_security: Option<UnderlyingSecurity>,
-    post_transaction_amounts: Option<PostTransactionAmounts>,
-    ownership_nature: Option<OwnershipNature>,
+    pub(crate) security_title: Option<ValueFootnote>,
+    pub(crate) conversion_or_exercise_price: Option<ValueFootnote>,
+    pub(crate) transaction_date: Option<ValueFootnote>,
+    pub(crate) deemed_execution_date: Option<ValueFootnote>,
+    pub(crate) transaction_coding: Option<TransactionCoding>,
+    pub(crate) transaction_timeliness: Option<ValueFootnote>,
+    pub(crate) transaction_amounts: Option<DerivativeTransactionAmounts>,
+    pub(crate) exercise_date: Option<ValueFootnote>,
+    pub(crate) expiration_date: Option<ValueFootnote>,
+    pub(crate) underlying_security: Option<UnderlyingSecurity>,
+    pub(crate) post_transaction_amounts: Option<PostTransactionAmounts>,
+    pub(crate) ownership_nature: Option<OwnershipNature>,
 }
 
 #[derive(NifMap)]
@@ -124,94 +129,142 @@ pub struct DerivativeHolding {
     ownership_nature: Option<OwnershipNature>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct TransactionCoding {
     form_type: Option<String>,
-    transaction_code: Option<String>,
-    equity_swap_involved: Option<bool>,
-    footnote_id: Option<String>,
+    pub(crate) transaction_code: Option<String>,
+    pub(crate) equity_swap_involved: Option<bool>,
+    pub(crate) footnote_ids: Vec<String>,
+    pub(crate) footnotes: Vec<String>,
 }
 
 #[derive(NifMap)]
 pub struct HoldingCoding {
     form_type: Option<String>,
-    footnote_id: Option<String>,
+    footnote_ids: Vec<String>,
+    footnotes: Vec<String>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct TransactionAmounts {
-    shares: Option<ValueFootnote>,
-    price_per_share: Option<ValueFootnote>,
-    acquired_disposed_code: Option<ValueFootnote>,
+    pub(crate) shares: Option<ValueFootnote>,
+    pub(crate) price_per_share: Option<ValueFootnote>,
+    pub(crate) acquired_disposed_code: Option<ValueFootnote>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct DerivativeTransactionAmounts {
-    shares: Option<ValueFootnote>,
-    price_per_share: Option<ValueFootnote>,
-    total_value: Option<ValueFootnote>,
-    acquired_disposed_code: Option<ValueFootnote>,
+    pub(crate) shares: Option<ValueFootnote>,
+    pub(crate) price_per_share: Option<ValueFootnote>,
+    pub(crate) total_value: Option<ValueFootnote>,
+    pub(crate) acquired_disposed_code: Option<ValueFootnote>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct UnderlyingSecurity {
-    title: Option<ValueFootnote>,
-    shares: Option<ValueFootnote>,
+    pub(crate) title: Option<ValueFootnote>,
+    pub(crate) shares: Option<ValueFootnote>,
     value: Option<ValueFootnote>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct PostTransactionAmounts {
-    shares_owned_following_transaction: Option<ValueFootnote>,
+    pub(crate) shares_owned_following_transaction: Option<ValueFootnote>,
     value_owned_following_transaction: Option<ValueFootnote>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct OwnershipNature {
-    direct_or_indirect_ownership: Option<ValueFootnote>,
-    nature_of_ownership: Option<ValueFootnote>,
+    pub(crate) direct_or_indirect_ownership: Option<ValueFootnote>,
+    pub(crate) nature_of_ownership: Option<ValueFootnote>,
 }
 
 #[derive(NifMap)]
 pub struct Footnote {
-    id: Option<String>,
-    note: Option<String>,
+    pub(crate) id: Option<String>,
+    pub(crate) note: Option<String>,
 }
 
 #[derive(NifMap)]
 pub struct OwnerSignature {
     name: String,
-    date: String,
+    date: Option<String>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct ValueFootnote {
-    value: Option<Value>,
-    footnote_id: Option<String>,
+    pub(crate) value: Option<Value>,
+    pub(crate) footnote_ids: Vec<String>,
+    pub(crate) footnotes: Vec<String>,
+}
+
+// Resolved footnote texts follow from the ids, and only one of two forms may
+// have been parsed with them resolved, so they are left out when comparing.
+impl PartialEq for ValueFootnote {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.footnote_ids == other.footnote_ids
+    }
+}
+
+#[derive(NifMap)]
+pub struct OwnershipOptions {
+    resolve_footnotes: bool,
+}
+
+#[derive(NifMap)]
+pub struct AmendedTransactions {
+    non_derivative_transactions: Vec<AmendedNonDerivativeTransaction>,
+    derivative_transactions: Vec<AmendedDerivativeTransaction>,
+}
+
+#[derive(NifMap)]
+pub struct AmendedNonDerivativeTransaction {
+    document_type: String,
+    period_of_report: Option<String>,
+    status: String,
+    changed_fields: Vec<String>,
+    transaction: NonDerivativeTransaction,
+}
+
+#[derive(NifMap)]
+pub struct AmendedDerivativeTransaction {
+    document_type: String,
+    period_of_report: Option<String>,
+    status: String,
+    changed_fields: Vec<String>,
+    transaction: DerivativeTransaction,
 }
 
 #[rustler::nif]
-pub fn parse_ownership_form(xml: &str) -> Result<Document, String> {
-    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+pub fn parse_ownership_form(xml: &str, options: OwnershipOptions) -> Result<Document, String> {
+    let doc = XMLDoc::parse(ownership_xml(xml)).map_err(|e| e.to_string())?;
     let root_node = doc.root_element();
     let schema_version = get_string(&root_node, "schemaVersion").ok();
+    let legacy = is_legacy_schema(schema_version.as_deref());
+    let mut warnings = vec![];
     let document_type = get_string(&root_node, "documentType")?;
-    let period_of_report = get_string(&root_node, "periodOfReport")?;
+    let period_of_report = get_legacy_string(&root_node, "periodOfReport", legacy)?;
     let date_of_original_submission = get_string(&root_node, "dateOfOriginalSubmission").ok();
     let no_securities_owned = get_bool(&root_node, "noSecuritiesOwned").ok();
     let not_subject_to_section_16 = get_bool(&root_node, "notSubjectToSection16").ok();
     let form3_holdings_reported = get_bool(&root_node, "form3HoldingsReported").ok();
     let form4_transactions_reported = get_bool(&root_node, "form4TransactionsReported").ok();
     let aff10b5_one = get_bool(&root_node, "aff10b5One").ok();
-    let issuer = parse_issuer(&root_node)?;
-    let reporting_owner = parse_reporting_owner(&root_node)?;
+    let issuer = parse_issuer(&root_node, legacy)?;
+    // Joint filings have one reportingOwner per owner, the first is the
+    // designated filer.
+    let reporting_owners = parse_reporting_owners(&root_node)?;
+    let reporting_owner = reporting_owners
+        .first()
+        .cloned()
+        .ok_or("reportingOwner not found".to_string())?;
     let non_derivative_table = parse_non_derivative_table(&root_node)?;
     let derivative_table = parse_derivative_table(&root_node)?;
     let footnotes = parse_footnotes(&root_node)?;
     let remarks = get_string(&root_node, "remarks").ok();
-    let owner_signature = parse_owner_signature(&root_node)?;
+    let owner_signatures = parse_owner_signatures(&root_node, &mut warnings);
 
-    Ok(Document {
+    let mut document = Document {
         schema_version,
         document_type,
         period_of_report,
@@ -222,23 +275,69 @@ pub fn parse_ownership_form(xml: &str) -> Result<Document, String> {
         form4_transactions_reported,
         issuer,
         reporting_owner,
+        reporting_owners,
         aff10b5_one,
         non_derivative_table,
         derivative_table,
         footnotes,
         remarks,
-        owner_signature,
-    })
+        owner_signatures,
+        warnings,
+    };
+
+    if options.resolve_footnotes {
+        document.resolve_footnotes();
+    }
+
+    Ok(document)
+}
+
+// Filings are sometimes stored as the full submission text, with the document
+// wrapped in <XML> tags after the SEC header, or with blank lines before the
+// XML declaration. Only the ownershipDocument element is parsed then.
+fn ownership_xml(text: &str) -> &str {
+    let end_tag = "</ownershipDocument>";
+    match (text.find("<ownershipDocument"), text.rfind(end_tag)) {
+        (Some(start), Some(end)) if start < end => &text[start..end + end_tag.len()],
+        _ => text,
+    }
 }
 
-fn parse_issuer(node: &roxmltree::Node) -> Result<Issuer, String> {
+// Schemas before X0203 (2003 filings and conversions of paper forms) did not
+// require the period of report or trading symbol, neither do documents
+// without a schemaVersion, so they are only optional there.
+fn is_legacy_schema(schema_version: Option<&str>) -> bool {
+    schema_version
+        .and_then(|version| {
+            version
+                .trim()
+                .trim_start_matches(['X', 'x'])
+                .parse::<u32>()
+                .ok()
+        })
+        .is_none_or(|version| version < 203)
+}
+
+fn get_legacy_string(
+    node: &roxmltree::Node,
+    tag: &str,
+    legacy: bool,
+) -> Result<Option<String>, String> {
+    if legacy {
+        Ok(get_string(node, tag).ok())
+    } else {
+        get_string(node, tag).map(Some)
+    }
+}
+
+fn parse_issuer(node: &roxmltree::Node, legacy: bool) -> Result<Issuer, String> {
     node.children()
         .find(|node| node.has_tag_name("issuer"))
         .ok_or("issuer not found".to_string())
         .and_then(|issuer_node| {
             let cik = get_string(&issuer_node, "issuerCik")?;
             let name = get_string(&issuer_node, "issuerName").ok();
-            let trading_symbol = get_string(&issuer_node, "issuerTradingSymbol")?;
+            let trading_symbol = get_legacy_string(&issuer_node, "issuerTradingSymbol", legacy)?;
 
             Ok(Issuer {
                 cik,
@@ -248,11 +347,10 @@ fn parse_issuer(node: &roxmltree::Node) -> Result<Issuer, String> {
         })
 }
 
-fn parse_reporting_owner(node: &roxmltree::Node) -> Result<ReportingOwner, String> {
+fn parse_reporting_owners(node: &roxmltree::Node) -> Result<Vec<ReportingOwner>, String> {
     node.children()
-        .find(|node| node.has_tag_name("reportingOwner"))
-        .ok_or("reportingOwner not found".to_string())
-        .and_then(|owner_node| {
+        .filter(|node| node.has_tag_name("reportingOwner"))
+        .map(|owner_node| {
             let id = parse_reporting_owner_id(&owner_node)?;
             let address = parse_reporting_owner_address(&owner_node)?;
             let relationship = parse_reporting_owner_relationship(&owner_node)?;
@@ -263,6 +361,7 @@ fn parse_reporting_owner(node: &roxmltree::Node) -> Result<ReportingOwner, Strin
                 relationship,
             })
         })
+        .collect()
 }
 
 fn parse_reporting_owner_id(node: &roxmltree::Node) -> Result<ReportingOwnerID, String> {
@@ -290,6 +389,7 @@ fn parse_reporting_owner_address(
             let state = get_string(&address_node, "rptOwnerState").ok();
             let zip_code = get_string(&address_node, "rptOwnerZipCode").ok();
             let state_description = get_string(&address_node, "rptOwnerStateDescription").ok();
+            let good_address = get_bool(&address_node, "rptOwnerGoodAddress").ok();
 
             Ok(ReportingOwnerAddress {
                 street1,
@@ -298,6 +398,7 @@ fn parse_reporting_owner_address(
                 state,
                 zip_code,
                 state_description,
+                good_address,
             })
         })
         .transpose()
@@ -371,7 +472,8 @@ fn parse_non_derivative_transactions(
             let transaction_date = get_value_footnote(&transaction_node, "transactionDate");
             let deemed_execution_date =
                 get_value_footnote(&transaction_node, "deemedExecutionDate");
-            let transaction_timeliness = get_value_footnote(&transaction_node, "transactionCoding");
+            let transaction_timeliness =
+                get_value_footnote(&transaction_node, "transactionTimeliness");
             let transaction_coding = parse_transaction_coding(&transaction_node).ok()?;
             let transaction_amounts = parse_transaction_amounts(&transaction_node).ok()?;
             let post_transaction_amounts =
@@ -403,6 +505,7 @@ fn parse_derivative_transactions(
             let security_title = get_value_footnote(&transaction_node, "securityTitle");
             let conversion_or_exercise_price =
                 get_value_footnote(&transaction_node, "conversionOrExercisePrice");
+            let transaction_date = get_value_footnote(&transaction_node, "transactionDate");
             let deemed_execution_date =
                 get_value_footnote(&transaction_node, "deemedExecutionDate");
             let transaction_coding = parse_transaction_coding(&transaction_node).ok()?;
@@ -420,6 +523,7 @@ fn parse_derivative_transactions(
             Some(DerivativeTransaction {
                 security_title,
                 conversion_or_exercise_price,
+                transaction_date,
                 deemed_execution_date,
                 transaction_coding,
                 transaction_timeliness,
@@ -495,13 +599,14 @@ fn parse_transaction_coding(node: &roxmltree::Node) -> Result<Option<Transaction
             let form_type = get_string(&coding_node, "transactionFormType").ok();
             let transaction_code = get_string(&coding_node, "transactionCode").ok();
             let equity_swap_involved = get_bool(&coding_node, "equitySwapInvolved").ok();
-            let footnote_id = get_string(&coding_node, "footnoteId").ok();
+            let footnote_ids = get_footnote_ids(&coding_node);
 
             Ok(TransactionCoding {
                 form_type,
                 transaction_code,
                 equity_swap_involved,
-                footnote_id,
+                footnote_ids,
+                footnotes: vec![],
             })
         })
         .transpose()
@@ -512,11 +617,12 @@ fn parse_holding_coding(node: &roxmltree::Node) -> Result<Option<HoldingCoding>,
         .find(|node| node.has_tag_name("transactionCoding"))
         .map(|coding_node| {
             let form_type = get_string(&coding_node, "transactionFormType").ok();
-            let footnote_id = get_string(&coding_node, "footnoteId").ok();
+            let footnote_ids = get_footnote_ids(&coding_node);
 
             Ok(HoldingCoding {
                 form_type,
-                footnote_id,
+                footnote_ids,
+                footnotes: vec![],
             })
         })
         .transpose()
@@ -620,26 +726,54 @@ fn parse_footnotes(node: &roxmltree::Node) -> Result<Vec<Footnote>, String> {
         .filter(|node| node.has_tag_name("footnotes"))
         .flat_map(|node| node.children())
         .filter(|node| node.has_tag_name("footnote"))
-        .filter_map(|footnote_node| {
+        .map(|footnote_node| {
             let id = footnote_node.attribute("id").map(|id| id.to_string());
             let note = footnote_node.text().map(|text| text.to_string());
 
-            Some(Footnote { id, note })
+            Footnote { id, note }
         })
         .collect();
     Ok(footnotes)
 }
 
-fn parse_owner_signature(node: &roxmltree::Node) -> Result<OwnerSignature, String> {
-    node.children()
-        .find(|node| node.has_tag_name("ownerSignature"))
-        .ok_or("ownerSignature not found".to_string())
-        .and_then(|signature_node| {
-            let name = get_string(&signature_node, "signatureName")?;
-            let date = get_string(&signature_node, "signatureDate")?;
+// Joint filings have one ownerSignature per reporting owner. Older filings
+// often omit the signature, so a missing one is a warning rather than an
+// error.
+fn parse_owner_signatures(
+    node: &roxmltree::Node,
+    warnings: &mut Vec<String>,
+) -> Vec<OwnerSignature> {
+    let signature_nodes: Vec<_> = node
+        .children()
+        .filter(|node| node.has_tag_name("ownerSignature"))
+        .collect();
 
-            Ok(OwnerSignature { name, date })
-        })
+    if signature_nodes.is_empty() {
+        warnings.push("ownerSignature not found".to_string());
+    }
+
+    let mut signatures = vec![];
+
+    for (index, signature_node) in signature_nodes.iter().enumerate() {
+        let Ok(name) = get_string(signature_node, "signatureName") else {
+            warnings.push(format!(
+                "ownerSignature {}: signatureName not found",
+                index + 1
+            ));
+            continue;
+        };
+        let date = get_string(signature_node, "signatureDate").ok();
+        if date.is_none() {
+            warnings.push(format!(
+                "ownerSignature {}: signatureDate not found",
+                index + 1
+            ));
+        }
+
+        signatures.push(OwnerSignature { name, date });
+    }
+
+    signatures
 }
 
 fn get_value_footnote(node: &roxmltree::Node, tag: &str) -> Option<ValueFootnote> {
@@ -652,12 +786,628 @@ fn get_value_footnote(node: &roxmltree::Node, tag: &str) -> Option<ValueFootnote
                 .and_then(|value_node| value_node.text())
                 .map(|s| parse_value(s.to_string()));
 
-            let footnote_id = tag_node
-                .children()
-                .find(|child_node| child_node.has_tag_name("footnoteId"))
-                .and_then(|id_node| id_node.attribute("id"))
-                .map(|s| s.to_string());
+            let footnote_ids = get_footnote_ids(&tag_node);
 
-            ValueFootnote { value, footnote_id }
+            ValueFootnote {
+                value,
+                footnote_ids,
+                footnotes: vec![],
+            }
         })
 }
+
+// Values often cite several footnotes, e.g. <footnoteId id="F1"/><footnoteId id="F2"/>.
+fn get_footnote_ids(node: &roxmltree::Node) -> Vec<String> {
+    node.children()
+        .filter(|child_node| child_node.has_tag_name("footnoteId"))
+        .filter_map(|id_node| id_node.attribute("id"))
+        .map(|id| id.to_string())
+        .collect()
+}
+
+// Footnote texts are resolved into the `footnotes` of each value and coding
+// that cites them, in the order of their ids. Unknown ids are skipped.
+trait ResolveFootnotes {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>);
+}
+
+impl Document {
+    fn resolve_footnotes(&mut self) {
+        let notes: HashMap<String, String> = self
+            .footnotes
+            .iter()
+            .filter_map(|footnote| {
+                let note = footnote.note.as_ref()?.trim().to_string();
+                Some((footnote.id.clone()?, note))
+            })
+            .collect();
+
+        self.non_derivative_table.resolve_footnotes(&notes);
+        self.derivative_table.resolve_footnotes(&notes);
+    }
+}
+
+impl<T: ResolveFootnotes> ResolveFootnotes for Option<T> {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        if let Some(inner) = self {
+            inner.resolve_footnotes(notes);
+        }
+    }
+}
+
+impl<T: ResolveFootnotes> ResolveFootnotes for Vec<T> {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        for item in self {
+            item.resolve_footnotes(notes);
+        }
+    }
+}
+
+fn footnote_texts(ids: &[String], notes: &HashMap<String, String>) -> Vec<String> {
+    ids.iter().filter_map(|id| notes.get(id).cloned()).collect()
+}
+
+impl ResolveFootnotes for ValueFootnote {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.footnotes = footnote_texts(&self.footnote_ids, notes);
+    }
+}
+
+impl ResolveFootnotes for TransactionCoding {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.footnotes = footnote_texts(&self.footnote_ids, notes);
+    }
+}
+
+impl ResolveFootnotes for HoldingCoding {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.footnotes = footnote_texts(&self.footnote_ids, notes);
+    }
+}
+
+impl ResolveFootnotes for NonDerivativeTable {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.transactions.resolve_footnotes(notes);
+        self.holdings.resolve_footnotes(notes);
+    }
+}
+
+impl ResolveFootnotes for DerivativeTable {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.transactions.resolve_footnotes(notes);
+        self.holdings.resolve_footnotes(notes);
+    }
+}
+
+impl ResolveFootnotes for NonDerivativeTransaction {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.security_title.resolve_footnotes(notes);
+        self.transaction_date.resolve_footnotes(notes);
+        self.deemed_execution_date.resolve_footnotes(notes);
+        self.transaction_coding.resolve_footnotes(notes);
+        self.transaction_timeliness.resolve_footnotes(notes);
+        self.transaction_amounts.resolve_footnotes(notes);
+        self.post_transaction_amounts.resolve_footnotes(notes);
+        self.ownership_nature.resolve_footnotes(notes);
+    }
+}
+
+impl ResolveFootnotes for DerivativeTransaction {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.security_title.resolve_footnotes(notes);
+        self.conversion_or_exercise_price.resolve_footnotes(notes);
+        self.transaction_date.resolve_footnotes(notes);
+        self.deemed_execution_date.resolve_footnotes(notes);
+        self.transaction_coding.resolve_footnotes(notes);
+        self.transaction_timeliness.resolve_footnotes(notes);
+        self.transaction_amounts.resolve_footnotes(notes);
+        self.exercise_date.resolve_footnotes(notes);
+        self.expiration_date.resolve_footnotes(notes);
+        self.underlying_security.resolve_footnotes(notes);
+        self.post_transaction_amounts.resolve_footnotes(notes);
+        self.ownership_nature.resolve_footnotes(notes);
+    }
+}
+
+impl ResolveFootnotes for NonDerivativeHolding {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.security_title.resolve_footnotes(notes);
+        self.transaction_coding.resolve_footnotes(notes);
+        self.post_transaction_amounts.resolve_footnotes(notes);
+        self.ownership_nature.resolve_footnotes(notes);
+    }
+}
+
+impl ResolveFootnotes for DerivativeHolding {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.security_title.resolve_footnotes(notes);
+        self.conversion_or_exercise_price.resolve_footnotes(notes);
+        self.transaction_coding.resolve_footnotes(notes);
+        self.exercise_date.resolve_footnotes(notes);
+        self.expiration_date.resolve_footnotes(notes);
+        self.underlying_security.resolve_footnotes(notes);
+        self.post_transaction_amounts.resolve_footnotes(notes);
+        self.ownership_nature.resolve_footnotes(notes);
+    }
+}
+
+impl ResolveFootnotes for TransactionAmounts {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.shares.resolve_footnotes(notes);
+        self.price_per_share.resolve_footnotes(notes);
+        self.acquired_disposed_code.resolve_footnotes(notes);
+    }
+}
+
+impl ResolveFootnotes for DerivativeTransactionAmounts {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.shares.resolve_footnotes(notes);
+        self.price_per_share.resolve_footnotes(notes);
+        self.total_value.resolve_footnotes(notes);
+        self.acquired_disposed_code.resolve_footnotes(notes);
+    }
+}
+
+impl ResolveFootnotes for UnderlyingSecurity {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.title.resolve_footnotes(notes);
+        self.shares.resolve_footnotes(notes);
+        self.value.resolve_footnotes(notes);
+    }
+}
+
+impl ResolveFootnotes for PostTransactionAmounts {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.shares_owned_following_transaction
+            .resolve_footnotes(notes);
+        self.value_owned_following_transaction
+            .resolve_footnotes(notes);
+    }
+}
+
+impl ResolveFootnotes for OwnershipNature {
+    fn resolve_footnotes(&mut self, notes: &HashMap<String, String>) {
+        self.direct_or_indirect_ownership.resolve_footnotes(notes);
+        self.nature_of_ownership.resolve_footnotes(notes);
+    }
+}
+
+// Originals are applied first and amendments in the given order. An amended
+// transaction replaces the effective one with the same period of report,
+// security, date and code, preferring the one at the same position in the
+// table when several share them. When none has the same date, the one at the
+// same position with the same security and code is replaced, so an amendment
+// can correct a transaction date. Amendment transactions that match none are
+// added, and transactions an amendment leaves out are kept since it may only
+// restate the ones that changed.
+#[rustler::nif]
+pub fn merge_ownership_amendments(documents: Vec<Document>) -> Result<AmendedTransactions, String> {
+    let same_parties = documents.windows(2).all(|pair| {
+        cik_eq(&pair[0].issuer.cik, &pair[1].issuer.cik)
+            && cik_eq(
+                &pair[0].reporting_owner.id.cik,
+                &pair[1].reporting_owner.id.cik,
+            )
+    });
+    if !same_parties {
+        return Err("documents must have the same issuer and reporting owner".to_string());
+    }
+
+    let mut documents: Vec<&Document> = documents.iter().collect();
+    documents.sort_by_key(|document| is_amendment(document));
+
+    let non_derivative_transactions = merge_transactions(&documents, |document| {
+        document
+            .non_derivative_table
+            .as_ref()
+            .map(|table| table.transactions.as_slice())
+            .unwrap_or_default()
+    })
+    .into_iter()
+    .map(|amended| AmendedNonDerivativeTransaction {
+        document_type: amended.document_type,
+        period_of_report: amended.period_of_report,
+        status: amended.status.to_string(),
+        changed_fields: amended.changed_fields,
+        transaction: amended.transaction,
+    })
+    .collect();
+
+    let derivative_transactions = merge_transactions(&documents, |document| {
+        document
+            .derivative_table
+            .as_ref()
+            .map(|table| table.transactions.as_slice())
+            .unwrap_or_default()
+    })
+    .into_iter()
+    .map(|amended| AmendedDerivativeTransaction {
+        document_type: amended.document_type,
+        period_of_report: amended.period_of_report,
+        status: amended.status.to_string(),
+        changed_fields: amended.changed_fields,
+        transaction: amended.transaction,
+    })
+    .collect();
+
+    Ok(AmendedTransactions {
+        non_derivative_transactions,
+        derivative_transactions,
+    })
+}
+
+trait Amendable: Clone {
+    fn key(&self) -> (Option<String>, Option<String>, Option<String>);
+    fn changed_fields(&self, other: &Self) -> Vec<String>;
+}
+
+impl Amendable for NonDerivativeTransaction {
+    fn key(&self) -> (Option<String>, Option<String>, Option<String>) {
+        (
+            value_text(&self.security_title),
+            value_text(&self.transaction_date),
+            transaction_code(&self.transaction_coding),
+        )
+    }
+
+    fn changed_fields(&self, other: &Self) -> Vec<String> {
+        let mut changed = vec![];
+        let (left, right) = (Some(self), Some(other));
+        diff(&mut changed, "security_title", left, right, |t| {
+            &t.security_title
+        });
+        diff(&mut changed, "transaction_date", left, right, |t| {
+            &t.transaction_date
+        });
+        diff(&mut changed, "deemed_execution_date", left, right, |t| {
+            &t.deemed_execution_date
+        });
+        diff(&mut changed, "transaction_timeliness", left, right, |t| {
+            &t.transaction_timeliness
+        });
+        diff_transaction_coding(
+            &mut changed,
+            &self.transaction_coding,
+            &other.transaction_coding,
+        );
+
+        let (left, right) = (
+            self.transaction_amounts.as_ref(),
+            other.transaction_amounts.as_ref(),
+        );
+        diff(
+            &mut changed,
+            "transaction_amounts.shares",
+            left,
+            right,
+            |a| &a.shares,
+        );
+        diff(
+            &mut changed,
+            "transaction_amounts.price_per_share",
+            left,
+            right,
+            |a| &a.price_per_share,
+        );
+        diff(
+            &mut changed,
+            "transaction_amounts.acquired_disposed_code",
+            left,
+            right,
+            |a| &a.acquired_disposed_code,
+        );
+
+        diff_post_transaction_amounts(
+            &mut changed,
+            &self.post_transaction_amounts,
+            &other.post_transaction_amounts,
+        );
+        diff_ownership_nature(
+            &mut changed,
+            &self.ownership_nature,
+            &other.ownership_nature,
+        );
+        changed
+    }
+}
+
+impl Amendable for DerivativeTransaction {
+    fn key(&self) -> (Option<String>, Option<String>, Option<String>) {
+        (
+            value_text(&self.security_title),
+            value_text(&self.transaction_date),
+            transaction_code(&self.transaction_coding),
+        )
+    }
+
+    fn changed_fields(&self, other: &Self) -> Vec<String> {
+        let mut changed = vec![];
+        let (left, right) = (Some(self), Some(other));
+        diff(&mut changed, "security_title", left, right, |t| {
+            &t.security_title
+        });
+        diff(
+            &mut changed,
+            "conversion_or_exercise_price",
+            left,
+            right,
+            |t| &t.conversion_or_exercise_price,
+        );
+        diff(&mut changed, "transaction_date", left, right, |t| {
+            &t.transaction_date
+        });
+        diff(&mut changed, "deemed_execution_date", left, right, |t| {
+            &t.deemed_execution_date
+        });
+        diff(&mut changed, "transaction_timeliness", left, right, |t| {
+            &t.transaction_timeliness
+        });
+        diff(&mut changed, "exercise_date", left, right, |t| {
+            &t.exercise_date
+        });
+        diff(&mut changed, "expiration_date", left, right, |t| {
+            &t.expiration_date
+        });
+        diff_transaction_coding(
+            &mut changed,
+            &self.transaction_coding,
+            &other.transaction_coding,
+        );
+
+        let (left, right) = (
+            self.transaction_amounts.as_ref(),
+            other.transaction_amounts.as_ref(),
+        );
+        diff(
+            &mut changed,
+            "transaction_amounts.shares",
+            left,
+            right,
+            |a| &a.shares,
+        );
+        diff(
+            &mut changed,
+            "transaction_amounts.price_per_share",
+            left,
+            right,
+            |a| &a.price_per_share,
+        );
+        diff(
+            &mut changed,
+            "transaction_amounts.total_value",
+            left,
+            right,
+            |a| &a.total_value,
+        );
+        diff(
+            &mut changed,
+            "transaction_amounts.acquired_disposed_code",
+            left,
+            right,
+            |a| &a.acquired_disposed_code,
+        );
+
+        let (left, right) = (
+            self.underlying_security.as_ref(),
+            other.underlying_security.as_ref(),
+        );
+        diff(
+            &mut changed,
+            "underlying_security.title",
+            left,
+            right,
+            |u| &u.title,
+        );
+        diff(
+            &mut changed,
+            "underlying_security.shares",
+            left,
+            right,
+            |u| &u.shares,
+        );
+        diff(
+            &mut changed,
+            "underlying_security.value",
+            left,
+            right,
+            |u| &u.value,
+        );
+
+        diff_post_transaction_amounts(
+            &mut changed,
+            &self.post_transaction_amounts,
+            &other.post_transaction_amounts,
+        );
+        diff_ownership_nature(
+            &mut changed,
+            &self.ownership_nature,
+            &other.ownership_nature,
+        );
+        changed
+    }
+}
+
+struct Amended<T> {
+    document_type: String,
+    period_of_report: Option<String>,
+    status: &'static str,
+    changed_fields: Vec<String>,
+    transaction: T,
+}
+
+fn merge_transactions<'a, T: Amendable + 'a>(
+    documents: &[&'a Document],
+    transactions: impl Fn(&'a Document) -> &'a [T],
+) -> Vec<Amended<T>> {
+    let mut merged: Vec<Amended<T>> = vec![];
+
+    for document in documents {
+        let amendment = is_amendment(document);
+        let mut matched = HashSet::new();
+        // The effective transactions of the same period, in table order.
+        let period_indexes: Vec<usize> = if amendment {
+            (0..merged.len())
+                .filter(|&index| merged[index].period_of_report == document.period_of_report)
+                .collect()
+        } else {
+            vec![]
+        };
+
+        for (position, transaction) in transactions(document).iter().enumerate() {
+            let key = transaction.key();
+            let positional = period_indexes
+                .get(position)
+                .copied()
+                .filter(|index| !matched.contains(index));
+            let index = positional
+                .filter(|&index| merged[index].transaction.key() == key)
+                .or_else(|| {
+                    period_indexes.iter().copied().find(|index| {
+                        !matched.contains(index) && merged[*index].transaction.key() == key
+                    })
+                })
+                .or_else(|| {
+                    positional.filter(|&index| {
+                        let (title, _, code) = merged[index].transaction.key();
+                        title == key.0 && code == key.2
+                    })
+                });
+
+            let Some(index) = index else {
+                merged.push(Amended {
+                    document_type: document.document_type.clone(),
+                    period_of_report: document.period_of_report.clone(),
+                    status: if amendment { "added" } else { "original" },
+                    changed_fields: vec![],
+                    transaction: transaction.clone(),
+                });
+                continue;
+            };
+            matched.insert(index);
+
+            let effective = &mut merged[index];
+            let changed_fields = effective.transaction.changed_fields(transaction);
+            if changed_fields.is_empty() {
+                continue;
+            }
+
+            for field in changed_fields {
+                if !effective.changed_fields.contains(&field) {
+                    effective.changed_fields.push(field);
+                }
+            }
+            effective.document_type = document.document_type.clone();
+            if effective.status == "original" {
+                effective.status = "amended";
+            }
+            effective.transaction = transaction.clone();
+        }
+    }
+
+    merged
+}
+
+fn is_amendment(document: &Document) -> bool {
+    document.document_type.ends_with("/A") || document.date_of_original_submission.is_some()
+}
+
+fn cik_eq(left: &str, right: &str) -> bool {
+    left.trim().trim_start_matches('0') == right.trim().trim_start_matches('0')
+}
+
+fn diff<P, T: PartialEq>(
+    changed: &mut Vec<String>,
+    name: &str,
+    left: Option<&P>,
+    right: Option<&P>,
+    field: impl Fn(&P) -> &Option<T>,
+) {
+    if left.and_then(|p| field(p).as_ref()) != right.and_then(|p| field(p).as_ref()) {
+        changed.push(name.to_string());
+    }
+}
+
+fn diff_transaction_coding(
+    changed: &mut Vec<String>,
+    left: &Option<TransactionCoding>,
+    right: &Option<TransactionCoding>,
+) {
+    let (left, right) = (left.as_ref(), right.as_ref());
+    diff(changed, "transaction_coding.form_type", left, right, |c| {
+        &c.form_type
+    });
+    diff(
+        changed,
+        "transaction_coding.transaction_code",
+        left,
+        right,
+        |c| &c.transaction_code,
+    );
+    diff(
+        changed,
+        "transaction_coding.equity_swap_involved",
+        left,
+        right,
+        |c| &c.equity_swap_involved,
+    );
+    if left.map(|c| &c.footnote_ids) != right.map(|c| &c.footnote_ids) {
+        changed.push("transaction_coding.footnote_ids".to_string());
+    }
+}
+
+fn diff_post_transaction_amounts(
+    changed: &mut Vec<String>,
+    left: &Option<PostTransactionAmounts>,
+    right: &Option<PostTransactionAmounts>,
+) {
+    let (left, right) = (left.as_ref(), right.as_ref());
+    diff(
+        changed,
+        "post_transaction_amounts.shares_owned_following_transaction",
+        left,
+        right,
+        |a| &a.shares_owned_following_transaction,
+    );
+    diff(
+        changed,
+        "post_transaction_amounts.value_owned_following_transaction",
+        left,
+        right,
+        |a| &a.value_owned_following_transaction,
+    );
+}
+
+fn diff_ownership_nature(
+    changed: &mut Vec<String>,
+    left: &Option<OwnershipNature>,
+    right: &Option<OwnershipNature>,
+) {
+    let (left, right) = (left.as_ref(), right.as_ref());
+    diff(
+        changed,
+        "ownership_nature.direct_or_indirect_ownership",
+        left,
+        right,
+        |n| &n.direct_or_indirect_ownership,
+    );
+    diff(
+        changed,
+        "ownership_nature.nature_of_ownership",
+        left,
+        right,
+        |n| &n.nature_of_ownership,
+    );
+}
+
+fn transaction_code(coding: &Option<TransactionCoding>) -> Option<String> {
+    coding.as_ref()?.transaction_code.clone()
+}
+
+fn value_text(value: &Option<ValueFootnote>) -> Option<String> {
+    match value.as_ref()?.value.as_ref()? {
+        Value::Int(value) => Some(value.to_string()),
+        Value::Float(value) => Some(value.to_string()),
+        Value::Text(text) => Some(text.trim().to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+    }
+}
@@ -0,0 +1,493 @@
+use crate::fiscal_periods::parse_date;
+use crate::standardize::LineItemRule;
+use crate::xbrl::{Document, FactIndex, Period};
+use rustler::NifMap;
+use std::collections::HashSet;
+
+// Ratios are arithmetic expressions over concepts and standardized line
+// items, evaluated for every period of the facts without
+// dimensions. A duration also sees the instants at its end date, so
+// NetIncome / TotalEquity uses the closing equity. prior(x) evaluates x for
+// the same period one year earlier, and coalesce(a, b, ..) the first argument
+// that can be evaluated.
+
+#[derive(NifMap)]
+pub struct RatioDefinition {
+    name: String,
+    expression: String,
+}
+
+#[derive(NifMap)]
+pub struct RatioValue {
+    name: String,
+    value: f64,
+    unit: Option<String>,
+    period: Period,
+    inputs: Vec<FactReference>,
+}
+
+#[derive(NifMap)]
+pub struct FactReference {
+    concept: String,
+    value: f64,
+    unit: Option<String>,
+    period: Period,
+}
+
+const DEFAULT_RATIOS: &[(&str, &str)] = &[
+    ("GrossMargin", "GrossProfit / Revenue"),
+    ("OperatingMargin", "OperatingIncome / Revenue"),
+    ("NetMargin", "NetIncome / Revenue"),
+    ("CurrentRatio", "CurrentAssets / CurrentLiabilities"),
+    ("ReturnOnEquity", "NetIncome / TotalEquity"),
+    ("ReturnOnAssets", "NetIncome / TotalAssets"),
+    ("DebtToEquity", "TotalLiabilities / TotalEquity"),
+    ("EquityMultiplier", "TotalAssets / TotalEquity"),
+    ("RevenueGrowth", "Revenue / prior(Revenue) - 1"),
+    ("NetIncomeGrowth", "NetIncome / prior(NetIncome) - 1"),
+];
+
+#[rustler::nif]
+pub fn default_ratios() -> Vec<RatioDefinition> {
+    DEFAULT_RATIOS
+        .iter()
+        .map(|(name, expression)| RatioDefinition {
+            name: name.to_string(),
+            expression: expression.to_string(),
+        })
+        .collect()
+}
+
+#[rustler::nif]
+pub fn compute_ratios(
+    document: Document,
+    ratios: Vec<RatioDefinition>,
+    line_items: Vec<LineItemRule>,
+) -> Result<Vec<RatioValue>, String> {
+    let expressions = ratios
+        .iter()
+        .map(|ratio| {
+            parse_expression(&ratio.expression)
+                .and_then(|expr| expand_line_items(expr, &line_items, &mut vec![]))
+                .map_err(|e| {
+                    format!(
+                        "{}: invalid expression: {}: {}",
+                        ratio.name, ratio.expression, e
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let evaluator = Evaluator {
+        facts: FactIndex::new(&document),
+    };
+
+    let mut values = vec![];
+
+    for (ratio, expression) in ratios.iter().zip(&expressions) {
+        for period in &evaluator.facts.periods {
+            let Some(operand) = evaluator.evaluate(expression, period) else {
+                continue;
+            };
+            // Durations also see instants, so an expression over instants
+            // only is reported for the instants themselves.
+            if period.start_date.is_some() && !operand.has_duration {
+                continue;
+            }
+            if !operand.value.is_finite() {
+                continue;
+            }
+
+            let mut seen = HashSet::new();
+            let inputs = operand
+                .inputs
+                .into_iter()
+                .filter(|input| seen.insert((input.concept.clone(), input.period.key())))
+                .collect();
+
+            values.push(RatioValue {
+                name: ratio.name.clone(),
+                value: operand.value,
+                unit: operand.unit,
+                period: (*period).clone(),
+                inputs,
+            });
+        }
+    }
+
+    Ok(values)
+}
+
+enum Expr {
+    Number(f64),
+    Concept(String),
+    Negate(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+    Prior(Box<Expr>),
+    Coalesce(Vec<Expr>),
+}
+
+#[derive(Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Symbol(char),
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = text
+                .parse()
+                .map_err(|_| format!("invalid number {}", text))?;
+            tokens.push(Token::Number(number));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            // Prefixes such as us-gaap contain dashes, which are otherwise
+            // read as a minus.
+            let start = i;
+            let mut end = i;
+            while end < chars.len() && (is_name_char(chars[end]) || chars[end] == '-') {
+                end += 1;
+            }
+            if end < chars.len() && chars[end] == ':' {
+                i = end + 1;
+            }
+            while i < chars.len() && is_name_char(chars[i]) {
+                i += 1;
+            }
+            tokens.push(Token::Name(chars[start..i].iter().collect()));
+        } else if "+-*/(),".contains(c) {
+            tokens.push(Token::Symbol(c));
+            i += 1;
+        } else {
+            return Err(format!("unexpected {}", c));
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_expression(expression: &str) -> Result<Expr, String> {
+    let mut parser = Parser {
+        tokens: tokenize(expression)?,
+        position: 0,
+    };
+    let expr = parser.expression()?;
+
+    match parser.next() {
+        None => Ok(expr),
+        Some(token) => Err(format!("unexpected {}", token_to_string(&token))),
+    }
+}
+
+fn token_to_string(token: &Token) -> String {
+    match token {
+        Token::Number(number) => number.to_string(),
+        Token::Name(name) => name.clone(),
+        Token::Symbol(symbol) => symbol.to_string(),
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        let found = self.tokens.get(self.position) == Some(&Token::Symbol(symbol));
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Symbol(found)) if found == symbol => Ok(()),
+            Some(token) => Err(format!(
+                "expected {}, found {}",
+                symbol,
+                token_to_string(&token)
+            )),
+            None => Err(format!("expected {}", symbol)),
+        }
+    }
+
+    // expression = term (("+" | "-") term)*
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut left = self.term()?;
+        loop {
+            let operator = match self.tokens.get(self.position) {
+                Some(Token::Symbol(c @ ('+' | '-'))) => *c,
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            left = Expr::Binary(operator, Box::new(left), Box::new(self.term()?));
+        }
+    }
+
+    // term = unary (("*" | "/") unary)*
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        loop {
+            let operator = match self.tokens.get(self.position) {
+                Some(Token::Symbol(c @ ('*' | '/'))) => *c,
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            left = Expr::Binary(operator, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat('-') {
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(number)) => Ok(Expr::Number(number)),
+            Some(Token::Symbol('(')) => {
+                let expr = self.expression()?;
+                self.expect(')')?;
+                Ok(expr)
+            }
+            Some(Token::Name(name)) if self.eat('(') => {
+                let mut args = vec![self.expression()?];
+                while self.eat(',') {
+                    args.push(self.expression()?);
+                }
+                self.expect(')')?;
+
+                match (name.as_str(), args.len()) {
+                    ("prior", 1) => Ok(Expr::Prior(Box::new(args.remove(0)))),
+                    ("coalesce", _) => Ok(Expr::Coalesce(args)),
+                    _ => Err(format!("unknown function {}/{}", name, args.len())),
+                }
+            }
+            Some(Token::Name(name)) => Ok(Expr::Concept(name)),
+            Some(token) => Err(format!("unexpected {}", token_to_string(&token))),
+            None => Err("unexpected end".to_string()),
+        }
+    }
+}
+
+// Names of standardized line items stand for their concepts and then their
+// formulas, e.g. Revenue for coalesce(Revenues, ..). Prefixed names always
+// refer to concepts. As in the standardized statements, a line item referring
+// back to one being expanded is never evaluated, so OperatingIncome and
+// OperatingExpenses cannot loop.
+fn expand_line_items(
+    expr: Expr,
+    line_items: &[LineItemRule],
+    expanding: &mut Vec<String>,
+) -> Result<Expr, String> {
+    let expand = |expr: Box<Expr>, expanding: &mut Vec<String>| {
+        expand_line_items(*expr, line_items, expanding).map(Box::new)
+    };
+
+    match expr {
+        Expr::Concept(name) => {
+            let Some(rule) = line_items.iter().find(|rule| rule.name == name) else {
+                return Ok(Expr::Concept(name));
+            };
+            if expanding.contains(&name) {
+                return Ok(Expr::Coalesce(vec![]));
+            }
+
+            expanding.push(name);
+            let mut exprs: Vec<Expr> = rule
+                .concepts
+                .iter()
+                .map(|concept| Expr::Concept(concept.clone()))
+                .collect();
+            for formula in &rule.formulas {
+                let expr = parse_expression(formula)
+                    .map_err(|e| format!("invalid formula: {}: {}", formula, e))?;
+                exprs.push(expand_line_items(expr, line_items, expanding)?);
+            }
+            expanding.pop();
+
+            Ok(Expr::Coalesce(exprs))
+        }
+        Expr::Negate(expr) => Ok(Expr::Negate(expand(expr, expanding)?)),
+        Expr::Binary(operator, left, right) => Ok(Expr::Binary(
+            operator,
+            expand(left, expanding)?,
+            expand(right, expanding)?,
+        )),
+        Expr::Prior(expr) => Ok(Expr::Prior(expand(expr, expanding)?)),
+        Expr::Coalesce(exprs) => Ok(Expr::Coalesce(
+            exprs
+                .into_iter()
+                .map(|expr| expand_line_items(expr, line_items, expanding))
+                .collect::<Result<_, _>>()?,
+        )),
+        expr => Ok(expr),
+    }
+}
+
+struct Operand {
+    value: f64,
+    unit: Option<String>,
+    inputs: Vec<FactReference>,
+    has_duration: bool,
+}
+
+struct Evaluator<'a> {
+    facts: FactIndex<'a>,
+}
+
+impl Evaluator<'_> {
+    fn evaluate(&self, expr: &Expr, period: &Period) -> Option<Operand> {
+        match expr {
+            Expr::Number(value) => Some(Operand {
+                value: *value,
+                unit: None,
+                inputs: vec![],
+                has_duration: false,
+            }),
+            Expr::Concept(concept) => self.lookup(concept, period),
+            Expr::Negate(expr) => {
+                let mut operand = self.evaluate(expr, period)?;
+                operand.value = -operand.value;
+                Some(operand)
+            }
+            Expr::Binary(operator, left, right) => {
+                let left = self.evaluate(left, period)?;
+                let right = self.evaluate(right, period)?;
+                let (value, unit) = match operator {
+                    '+' | '-' => {
+                        // Only amounts in the same unit can be added.
+                        let unit = match (left.unit, right.unit) {
+                            (Some(left), Some(right)) if left != right => return None,
+                            (left, right) => left.or(right),
+                        };
+                        match operator {
+                            '+' => (left.value + right.value, unit),
+                            _ => (left.value - right.value, unit),
+                        }
+                    }
+                    '*' => (
+                        left.value * right.value,
+                        multiply_units(left.unit, right.unit),
+                    ),
+                    _ if right.value == 0.0 => return None,
+                    _ => (
+                        left.value / right.value,
+                        divide_units(left.unit, right.unit),
+                    ),
+                };
+
+                let mut inputs = left.inputs;
+                inputs.extend(right.inputs);
+
+                Some(Operand {
+                    value,
+                    unit,
+                    inputs,
+                    has_duration: left.has_duration || right.has_duration,
+                })
+            }
+            Expr::Prior(expr) => self.evaluate(expr, self.prior_period(period)?),
+            Expr::Coalesce(exprs) => exprs.iter().find_map(|expr| self.evaluate(expr, period)),
+        }
+    }
+
+    fn lookup(&self, concept: &str, period: &Period) -> Option<Operand> {
+        let is_duration = period.start_date.is_some();
+        let (fact, has_duration) = match self.facts.get(concept, period.key()) {
+            Some(fact) => (fact, is_duration),
+            None if is_duration => {
+                let instant = (period.end_date.clone(), None, None);
+                (self.facts.get(concept, instant)?, false)
+            }
+            None => return None,
+        };
+        let value = fact.numeric_value()?;
+
+        Some(Operand {
+            value,
+            unit: fact.unit.clone(),
+            inputs: vec![FactReference {
+                concept: fact.concept.clone(),
+                value,
+                unit: fact.unit.clone(),
+                period: fact.context.period.clone(),
+            }],
+            has_duration,
+        })
+    }
+
+    // The period of the same length ending a year earlier, allowing a week
+    // either way for 52-53 week fiscal years.
+    fn prior_period(&self, period: &Period) -> Option<&Period> {
+        let end = end_date(period)?;
+        let length = start_date(period).map(|start| end - start);
+
+        self.facts.periods.iter().copied().find(|candidate| {
+            let Some(candidate_end) = end_date(candidate) else {
+                return false;
+            };
+            let candidate_length = start_date(candidate).map(|start| candidate_end - start);
+            let same_length = match (length, candidate_length) {
+                (Some(length), Some(candidate_length)) => (length - candidate_length).abs() <= 7,
+                (None, None) => true,
+                _ => false,
+            };
+
+            same_length && (358..=372).contains(&(end - candidate_end))
+        })
+    }
+}
+
+fn start_date(period: &Period) -> Option<i64> {
+    period.start_date.as_deref().and_then(parse_date)
+}
+
+fn end_date(period: &Period) -> Option<i64> {
+    period
+        .end_date
+        .as_deref()
+        .or(period.instant.as_deref())
+        .and_then(parse_date)
+}
+
+fn multiply_units(left: Option<String>, right: Option<String>) -> Option<String> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(format!("{}*{}", left, right)),
+        (left, right) => left.or(right),
+    }
+}
+
+fn divide_units(left: Option<String>, right: Option<String>) -> Option<String> {
+    match (left, right) {
+        (Some(left), Some(right)) if left == right => Some("pure".to_string()),
+        (Some(left), Some(right)) => Some(format!("{}/{}", left, right)),
+        (left, None) => left,
+        (None, Some(right)) => Some(format!("1/{}", right)),
+    }
+}
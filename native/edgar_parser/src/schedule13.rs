@@ -0,0 +1,326 @@
+use crate::xml::{get_bool, get_string, parse_filer, parse_float64, Filer};
+use roxmltree::Document as XMLDoc;
+use rustler::NifMap;
+
+// https://www.sec.gov/edgar/filer/technical-specifications
+// Schedule 13D and 13G share one document shape here; element names that
+// differ between the two schemas are resolved in the parse functions.
+
+#[derive(NifMap)]
+pub struct Document {
+    schema_version: Option<String>,
+    submission_type: String,
+    previous_accession_number: Option<String>,
+    filer: Option<Filer>,
+    cover_page: CoverPage,
+    reporting_persons: Vec<ReportingPerson>,
+    items: Vec<Item>,
+    signatures: Vec<Signature>,
+}
+
+#[derive(NifMap)]
+pub struct CoverPage {
+    securities_class_title: Option<String>,
+    date_of_event: Option<String>,
+    previously_filed: Option<bool>,
+    issuer: Issuer,
+    designated_rules: Vec<String>,
+}
+
+#[derive(NifMap)]
+pub struct Issuer {
+    cik: String,
+    name: Option<String>,
+    cusip: Option<String>,
+    address: Option<Address>,
+}
+
+#[derive(NifMap)]
+pub struct Address {
+    street1: Option<String>,
+    street2: Option<String>,
+    city: Option<String>,
+    state_or_country: Option<String>,
+    zip_code: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct ReportingPerson {
+    id: ReportingPersonID,
+    member_of_group: Option<String>,
+    source_of_funds: Vec<String>,
+    legal_proceedings: Option<bool>,
+    citizenship_or_organization: Option<String>,
+    sole_voting_power: Option<f64>,
+    shared_voting_power: Option<f64>,
+    sole_dispositive_power: Option<f64>,
+    shared_dispositive_power: Option<f64>,
+    aggregate_amount_owned: Option<f64>,
+    excludes_certain_shares: Option<bool>,
+    percent_of_class: Option<f64>,
+    type_of_reporting_person: Vec<String>,
+    comment: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct ReportingPersonID {
+    cik: Option<String>,
+    name: String,
+}
+
+#[derive(NifMap)]
+pub struct Item {
+    name: String,
+    fields: Vec<ItemField>,
+}
+
+#[derive(NifMap)]
+pub struct ItemField {
+    name: String,
+    value: String,
+}
+
+#[derive(NifMap)]
+pub struct Signature {
+    reporting_person_name: Option<String>,
+    signature: Option<String>,
+    title: Option<String>,
+    date: Option<String>,
+}
+
+#[rustler::nif]
+pub fn parse_schedule13(xml: &str) -> Result<Document, String> {
+    let doc = XMLDoc::parse(xml).map_err(|e| e.to_string())?;
+    let root_node = doc.root_element();
+    let schema_version = get_string(&root_node, "schemaVersion").ok();
+
+    let header_data_node = root_node
+        .children()
+        .find(|node| node.has_tag_name("headerData"))
+        .ok_or("headerData not found".to_string())?;
+    let submission_type = get_string(&header_data_node, "submissionType")?;
+    let previous_accession_number = get_string(&header_data_node, "previousAccessionNumber").ok();
+    let filer = parse_filer(&header_data_node)?;
+
+    let form_data_node = root_node
+        .children()
+        .find(|node| node.has_tag_name("formData"))
+        .ok_or("formData not found".to_string())?;
+    let cover_page = parse_cover_page(&form_data_node)?;
+    let reporting_persons = parse_reporting_persons(&form_data_node)?;
+    let items = parse_items(&form_data_node)?;
+    let signatures = parse_signatures(&form_data_node)?;
+
+    Ok(Document {
+        schema_version,
+        submission_type,
+        previous_accession_number,
+        filer,
+        cover_page,
+        reporting_persons,
+        items,
+        signatures,
+    })
+}
+
+fn parse_cover_page(node: &roxmltree::Node) -> Result<CoverPage, String> {
+    node.children()
+        .find(|node| node.has_tag_name("coverPageHeader"))
+        .ok_or("coverPageHeader not found".to_string())
+        .and_then(|cover_page_node| {
+            let securities_class_title = get_string(&cover_page_node, "securitiesClassTitle").ok();
+            let date_of_event = get_string(&cover_page_node, "dateOfEvent")
+                .or_else(|_| get_string(&cover_page_node, "eventDateRequiresFilingThisStatement"))
+                .ok();
+            let previously_filed = get_bool(&cover_page_node, "previouslyFiledFlag").ok();
+            let issuer = parse_issuer(&cover_page_node)?;
+            let designated_rules = cover_page_node
+                .descendants()
+                .filter(|node| node.has_tag_name("designateRulePursuantThisScheduleFiled"))
+                .filter_map(|node| node.text())
+                .map(|s| s.trim().to_string())
+                .collect();
+
+            Ok(CoverPage {
+                securities_class_title,
+                date_of_event,
+                previously_filed,
+                issuer,
+                designated_rules,
+            })
+        })
+}
+
+fn parse_issuer(node: &roxmltree::Node) -> Result<Issuer, String> {
+    node.children()
+        .find(|node| node.has_tag_name("issuerInfo"))
+        .ok_or("issuerInfo not found".to_string())
+        .and_then(|issuer_node| {
+            let cik = get_string(&issuer_node, "issuerCIK")
+                .or_else(|_| get_string(&issuer_node, "issuerCik"))?;
+            let name = get_string(&issuer_node, "issuerName").ok();
+            let cusip = get_string(&issuer_node, "issuerCUSIP")
+                .or_else(|_| get_string(&issuer_node, "issuerCusip"))
+                .ok();
+            let address = parse_address(&issuer_node)?;
+
+            Ok(Issuer {
+                cik,
+                name,
+                cusip,
+                address,
+            })
+        })
+}
+
+fn parse_address(node: &roxmltree::Node) -> Result<Option<Address>, String> {
+    node.children()
+        .find(|node| {
+            node.has_tag_name("address")
+                || node.has_tag_name("issuerPrincipalExecutiveOfficeAddress")
+        })
+        .map(|address_node| {
+            let street1 = get_string(&address_node, "street1").ok();
+            let street2 = get_string(&address_node, "street2").ok();
+            let city = get_string(&address_node, "city").ok();
+            let state_or_country = get_string(&address_node, "stateOrCountry").ok();
+            let zip_code = get_string(&address_node, "zipCode").ok();
+
+            Ok(Address {
+                street1,
+                street2,
+                city,
+                state_or_country,
+                zip_code,
+            })
+        })
+        .transpose()
+}
+
+fn parse_reporting_persons(node: &roxmltree::Node) -> Result<Vec<ReportingPerson>, String> {
+    node.children()
+        .filter(|node| node.has_tag_name("reportingPersons"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("reportingPersonInfo"))
+        .chain(
+            node.children()
+                .filter(|node| node.has_tag_name("coverPageHeaderReportingPersonDetails")),
+        )
+        .enumerate()
+        .map(|(index, person_node)| {
+            parse_reporting_person(&person_node)
+                .map_err(|e| format!("reportingPerson {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+fn parse_reporting_person(node: &roxmltree::Node) -> Result<ReportingPerson, String> {
+    let id = ReportingPersonID {
+        cik: get_string(node, "reportingPersonCIK").ok(),
+        name: get_string(node, "reportingPersonName")?,
+    };
+    let member_of_group = get_string(node, "memberOfGroup")
+        .or_else(|_| get_string(node, "memberGroup"))
+        .ok();
+    let source_of_funds = get_strings(node, "fundType");
+    let legal_proceedings = get_bool(node, "legalProceedings").ok();
+    let citizenship_or_organization = get_string(node, "citizenshipOrOrganization").ok();
+    let sole_voting_power = get_nested_float64(node, "soleVotingPower")?;
+    let shared_voting_power = get_nested_float64(node, "sharedVotingPower")?;
+    let sole_dispositive_power = get_nested_float64(node, "soleDispositivePower")?;
+    let shared_dispositive_power = get_nested_float64(node, "sharedDispositivePower")?;
+    let aggregate_amount_owned = match get_nested_float64(node, "aggregateAmountOwned")? {
+        Some(amount) => Some(amount),
+        None => get_nested_float64(
+            node,
+            "reportingPersonBeneficiallyOwnedAggregateNumberOfShares",
+        )?,
+    };
+    let excludes_certain_shares = get_bool(node, "isAggregateExcludeShares").ok();
+    let percent_of_class = match get_nested_float64(node, "percentOfClass")? {
+        Some(percent) => Some(percent),
+        None => get_nested_float64(node, "classPercent")?,
+    };
+    let type_of_reporting_person = get_strings(node, "typeOfReportingPerson");
+    let comment = get_string(node, "commentContent").ok();
+
+    Ok(ReportingPerson {
+        id,
+        member_of_group,
+        source_of_funds,
+        legal_proceedings,
+        citizenship_or_organization,
+        sole_voting_power,
+        shared_voting_power,
+        sole_dispositive_power,
+        shared_dispositive_power,
+        aggregate_amount_owned,
+        excludes_certain_shares,
+        percent_of_class,
+        type_of_reporting_person,
+        comment,
+    })
+}
+
+fn parse_items(node: &roxmltree::Node) -> Result<Vec<Item>, String> {
+    let items = node
+        .children()
+        .filter(|node| node.has_tag_name("items1To7") || node.has_tag_name("items"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.is_element())
+        .map(|item_node| {
+            let name = item_node.tag_name().name().to_string();
+            let fields = item_node
+                .descendants()
+                .filter(|node| node.is_element() && !node.children().any(|n| n.is_element()))
+                .filter_map(|field_node| {
+                    field_node.text().map(|text| ItemField {
+                        name: field_node.tag_name().name().to_string(),
+                        value: text.trim().to_string(),
+                    })
+                })
+                .collect();
+
+            Item { name, fields }
+        })
+        .collect();
+    Ok(items)
+}
+
+fn parse_signatures(node: &roxmltree::Node) -> Result<Vec<Signature>, String> {
+    let signatures = node
+        .children()
+        .filter(|node| node.has_tag_name("signatureInformation"))
+        .flat_map(|signature_info_node| {
+            let reporting_person_name =
+                get_string(&signature_info_node, "reportingPersonName").ok();
+
+            signature_info_node
+                .children()
+                .filter(|node| node.has_tag_name("signatureDetails"))
+                .map(move |details_node| Signature {
+                    reporting_person_name: reporting_person_name.clone(),
+                    signature: get_string(&details_node, "signature").ok(),
+                    title: get_string(&details_node, "title").ok(),
+                    date: get_string(&details_node, "date").ok(),
+                })
+        })
+        .collect();
+    Ok(signatures)
+}
+
+fn get_strings(node: &roxmltree::Node, tag: &str) -> Vec<String> {
+    node.children()
+        .filter(|node| node.has_tag_name(tag))
+        .filter_map(|node| node.text())
+        .map(|s| s.trim().to_string())
+        .collect()
+}
+
+fn get_nested_float64(node: &roxmltree::Node, tag: &str) -> Result<Option<f64>, String> {
+    node.descendants()
+        .find(|node| node.has_tag_name(tag))
+        .and_then(|node| node.text())
+        .map(|text| parse_float64(text, tag))
+        .transpose()
+}
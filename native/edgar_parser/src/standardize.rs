@@ -0,0 +1,356 @@
+use crate::xbrl::{Document, FactIndex, Period, PeriodKey};
+use rustler::NifMap;
+use std::collections::{HashMap, HashSet};
+
+// Standardized line items are resolved per period from an ordered list of
+// concepts, falling back to formulas over other line items or concepts, e.g.
+// GrossProfit = "Revenue - CostOfRevenue". Only facts without dimensions are
+// used.
+
+#[derive(NifMap)]
+pub struct LineItemRule {
+    pub(crate) name: String,
+    pub(crate) concepts: Vec<String>,
+    pub(crate) formulas: Vec<String>,
+}
+
+#[derive(NifMap)]
+pub struct StandardizedValue {
+    line_item: String,
+    value: f64,
+    unit: Option<String>,
+    period: Period,
+    concept: Option<String>,
+    formula: Option<String>,
+    inputs: Vec<String>,
+}
+
+// (name, concepts, formulas)
+const DEFAULT_LINE_ITEMS: &[(&str, &[&str], &[&str])] = &[
+    (
+        "Revenue",
+        &[
+            "Revenues",
+            "RevenueFromContractWithCustomerExcludingAssessedTax",
+            "RevenueFromContractWithCustomerIncludingAssessedTax",
+            "SalesRevenueNet",
+            "SalesRevenueGoodsNet",
+            "SalesRevenueServicesNet",
+            "RevenuesNetOfInterestExpense",
+        ],
+        &[],
+    ),
+    (
+        "CostOfRevenue",
+        &[
+            "CostOfRevenue",
+            "CostOfGoodsAndServicesSold",
+            "CostOfGoodsSold",
+            "CostOfServices",
+        ],
+        &[],
+    ),
+    (
+        "GrossProfit",
+        &["GrossProfit"],
+        &["Revenue - CostOfRevenue"],
+    ),
+    (
+        "OperatingExpenses",
+        &["OperatingExpenses"],
+        &["GrossProfit - OperatingIncome"],
+    ),
+    (
+        "OperatingIncome",
+        &["OperatingIncomeLoss"],
+        &["GrossProfit - OperatingExpenses"],
+    ),
+    (
+        "NetIncome",
+        &[
+            "NetIncomeLoss",
+            "ProfitLoss",
+            "NetIncomeLossAvailableToCommonStockholdersBasic",
+        ],
+        &[],
+    ),
+    ("EpsBasic", &["EarningsPerShareBasic"], &[]),
+    (
+        "EpsDiluted",
+        &["EarningsPerShareDiluted", "EarningsPerShareBasicAndDiluted"],
+        &[],
+    ),
+    (
+        "CashAndEquivalents",
+        &[
+            "CashAndCashEquivalentsAtCarryingValue",
+            "CashCashEquivalentsRestrictedCashAndRestrictedCashEquivalents",
+            "Cash",
+        ],
+        &[],
+    ),
+    ("CurrentAssets", &["AssetsCurrent"], &[]),
+    (
+        "TotalAssets",
+        &["Assets"],
+        &["CurrentAssets + AssetsNoncurrent"],
+    ),
+    ("CurrentLiabilities", &["LiabilitiesCurrent"], &[]),
+    (
+        "TotalLiabilities",
+        &["Liabilities"],
+        &[
+            "CurrentLiabilities + LiabilitiesNoncurrent",
+            "LiabilitiesAndStockholdersEquity - TotalEquity",
+        ],
+    ),
+    (
+        "TotalEquity",
+        &[
+            "StockholdersEquity",
+            "StockholdersEquityIncludingPortionAttributableToNoncontrollingInterest",
+        ],
+        &["TotalAssets - TotalLiabilities"],
+    ),
+    (
+        "OperatingCashFlow",
+        &[
+            "NetCashProvidedByUsedInOperatingActivities",
+            "NetCashProvidedByUsedInOperatingActivitiesContinuingOperations",
+        ],
+        &[],
+    ),
+    (
+        "InvestingCashFlow",
+        &[
+            "NetCashProvidedByUsedInInvestingActivities",
+            "NetCashProvidedByUsedInInvestingActivitiesContinuingOperations",
+        ],
+        &[],
+    ),
+    (
+        "FinancingCashFlow",
+        &[
+            "NetCashProvidedByUsedInFinancingActivities",
+            "NetCashProvidedByUsedInFinancingActivitiesContinuingOperations",
+        ],
+        &[],
+    ),
+    (
+        "CapitalExpenditures",
+        &[
+            "PaymentsToAcquirePropertyPlantAndEquipment",
+            "PaymentsToAcquireProductiveAssets",
+        ],
+        &[],
+    ),
+    (
+        "FreeCashFlow",
+        &[],
+        &["OperatingCashFlow - CapitalExpenditures"],
+    ),
+];
+
+#[rustler::nif]
+pub fn default_line_items() -> Vec<LineItemRule> {
+    DEFAULT_LINE_ITEMS
+        .iter()
+        .map(|(name, concepts, formulas)| LineItemRule {
+            name: name.to_string(),
+            concepts: concepts.iter().map(|s| s.to_string()).collect(),
+            formulas: formulas.iter().map(|s| s.to_string()).collect(),
+        })
+        .collect()
+}
+
+#[rustler::nif]
+pub fn standardize_statements(
+    document: Document,
+    line_items: Vec<LineItemRule>,
+) -> Result<Vec<StandardizedValue>, String> {
+    let mut formulas = HashMap::new();
+    for rule in &line_items {
+        let parsed = rule
+            .formulas
+            .iter()
+            .map(|formula| parse_formula(formula))
+            .collect::<Result<Vec<_>, String>>()
+            .map_err(|e| format!("{}: {}", rule.name, e))?;
+        formulas.insert(rule.name.as_str(), parsed);
+    }
+
+    let facts = FactIndex::new(&document);
+    let periods = facts.periods.clone();
+
+    let mut resolver = Resolver {
+        rules: line_items
+            .iter()
+            .map(|rule| (rule.name.as_str(), rule))
+            .collect(),
+        formulas,
+        facts,
+        resolving: HashSet::new(),
+    };
+
+    let mut values = vec![];
+
+    for rule in &line_items {
+        for period in &periods {
+            if let Some(resolved) = resolver.resolve(&rule.name, &period.key()) {
+                values.push(StandardizedValue {
+                    line_item: rule.name.clone(),
+                    value: resolved.value,
+                    unit: resolved.unit,
+                    period: (*period).clone(),
+                    concept: resolved.concept,
+                    formula: resolved.formula,
+                    inputs: resolved.inputs,
+                });
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+struct Term {
+    sign: f64,
+    operand: String,
+}
+
+// Formulas are operands separated by + or -, e.g. "Revenue - CostOfRevenue".
+// Operators must be surrounded by whitespace since concept prefixes such as
+// us-gaap contain dashes.
+fn parse_formula(formula: &str) -> Result<Vec<Term>, String> {
+    let mut terms = vec![];
+    let mut sign = 1.0;
+    let mut expect_operand = true;
+
+    for token in formula.split_whitespace() {
+        match (token, expect_operand) {
+            ("+", true) => {}
+            ("-", true) => sign = -sign,
+            ("+", false) => {
+                sign = 1.0;
+                expect_operand = true;
+            }
+            ("-", false) => {
+                sign = -1.0;
+                expect_operand = true;
+            }
+            (operand, true) => {
+                terms.push(Term {
+                    sign,
+                    operand: operand.to_string(),
+                });
+                expect_operand = false;
+            }
+            (operand, false) => {
+                return Err(format!(
+                    "invalid formula: {}: unexpected {}",
+                    formula, operand
+                ))
+            }
+        }
+    }
+
+    if terms.is_empty() || expect_operand {
+        return Err(format!("invalid formula: {}", formula));
+    }
+
+    Ok(terms)
+}
+
+struct Resolved {
+    value: f64,
+    unit: Option<String>,
+    concept: Option<String>,
+    formula: Option<String>,
+    inputs: Vec<String>,
+}
+
+struct Resolver<'a> {
+    rules: HashMap<&'a str, &'a LineItemRule>,
+    formulas: HashMap<&'a str, Vec<Vec<Term>>>,
+    facts: FactIndex<'a>,
+    resolving: HashSet<String>,
+}
+
+impl Resolver<'_> {
+    // Operands name either another line item or a concept. Line items that
+    // are already being resolved are skipped so formulas that refer to each
+    // other (OperatingIncome and OperatingExpenses) cannot loop.
+    fn resolve(&mut self, name: &str, period: &PeriodKey) -> Option<Resolved> {
+        let Some(rule) = self.rules.get(name).copied() else {
+            return self.concept_value(name, period);
+        };
+        if !self.resolving.insert(name.to_string()) {
+            return None;
+        }
+
+        let resolved = rule
+            .concepts
+            .iter()
+            .find_map(|concept| self.concept_value(concept, period))
+            .or_else(|| {
+                rule.formulas
+                    .iter()
+                    .enumerate()
+                    .find_map(|(index, formula)| {
+                        let (value, unit, inputs) = self.evaluate(name, index, period)?;
+                        Some(Resolved {
+                            value,
+                            unit,
+                            concept: None,
+                            formula: Some(formula.clone()),
+                            inputs,
+                        })
+                    })
+            });
+
+        self.resolving.remove(name);
+        resolved
+    }
+
+    fn evaluate(
+        &mut self,
+        name: &str,
+        index: usize,
+        period: &PeriodKey,
+    ) -> Option<(f64, Option<String>, Vec<String>)> {
+        let operands: Vec<(f64, String)> = self.formulas.get(name)?[index]
+            .iter()
+            .map(|term| (term.sign, term.operand.clone()))
+            .collect();
+
+        let mut value = 0.0;
+        let mut unit = None;
+        let mut inputs = vec![];
+
+        for (sign, operand) in operands {
+            let resolved = self.resolve(&operand, period)?;
+            value += sign * resolved.value;
+            // Only amounts in the same unit can be added, so a formula mixing
+            // units falls through to the next one.
+            unit = match (unit, resolved.unit) {
+                (Some(left), Some(right)) if left != right => return None,
+                (left, right) => left.or(right),
+            };
+            inputs.extend(resolved.inputs);
+        }
+
+        Some((value, unit, inputs))
+    }
+
+    fn concept_value(&self, concept: &str, period: &PeriodKey) -> Option<Resolved> {
+        let fact = self.facts.get(concept, period.clone())?;
+
+        Some(Resolved {
+            value: fact.numeric_value()?,
+            unit: fact.unit.clone(),
+            concept: Some(fact.concept.clone()),
+            formula: None,
+            inputs: vec![fact.concept.clone()],
+        })
+    }
+}
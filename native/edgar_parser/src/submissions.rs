@@ -0,0 +1,349 @@
+use rustler::NifMap;
+use serde_json::{Map, Value};
+
+// https://data.sec.gov/submissions/CIK##########.json
+// Filings are stored column-wise: `filings.recent` holds one array per field
+// and older filings are split into paging files (`filings.files`) that use
+// the same layout at their top level.
+
+#[derive(NifMap)]
+pub struct Submissions {
+    cik: Option<String>,
+    name: Option<String>,
+    entity_type: Option<String>,
+    sic: Option<String>,
+    sic_description: Option<String>,
+    tickers: Vec<String>,
+    exchanges: Vec<String>,
+    fiscal_year_end: Option<String>,
+    state_of_incorporation: Option<String>,
+    filings: Vec<Filing>,
+    files: Vec<SubmissionsFile>,
+}
+
+#[derive(NifMap)]
+pub struct Filing {
+    accession_number: String,
+    filing_date: String,
+    report_date: Option<String>,
+    acceptance_date_time: Option<String>,
+    act: Option<String>,
+    form: String,
+    file_number: Option<String>,
+    film_number: Option<String>,
+    items: Vec<String>,
+    size: Option<i64>,
+    is_xbrl: bool,
+    is_inline_xbrl: bool,
+    primary_document: Option<String>,
+    primary_doc_description: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SubmissionsFile {
+    name: String,
+    filing_count: Option<i64>,
+    filing_from: Option<String>,
+    filing_to: Option<String>,
+}
+
+#[derive(NifMap)]
+pub struct SubmissionsFilter {
+    form_types: Option<Vec<String>>,
+    date_from: Option<String>,
+    date_to: Option<String>,
+}
+
+impl SubmissionsFilter {
+    fn matches(&self, filing: &Filing) -> bool {
+        let form_matches = self
+            .form_types
+            .as_ref()
+            .map(|form_types| form_types.contains(&filing.form))
+            .unwrap_or(true);
+        // Dates are validated as YYYY-MM-DD, so comparing strings orders them.
+        let from_matches = self
+            .date_from
+            .as_ref()
+            .map(|date_from| filing.filing_date.as_str() >= date_from.as_str())
+            .unwrap_or(true);
+        let to_matches = self
+            .date_to
+            .as_ref()
+            .map(|date_to| filing.filing_date.as_str() <= date_to.as_str())
+            .unwrap_or(true);
+
+        form_matches && from_matches && to_matches
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for date in self.date_from.iter().chain(self.date_to.iter()) {
+            parse_date(date, "filter")?;
+        }
+        Ok(())
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn parse_submissions(json: &str, filter: SubmissionsFilter) -> Result<Submissions, String> {
+    filter.validate()?;
+
+    let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let root = value
+        .as_object()
+        .ok_or("expected a json object".to_string())?;
+
+    // The main submissions file nests the columns under filings.recent, while
+    // the paging files have them at the top level.
+    let filings_object = root.get("filings").and_then(|filings| filings.as_object());
+    let columns = match filings_object {
+        Some(filings_object) => filings_object
+            .get("recent")
+            .and_then(|recent| recent.as_object())
+            .ok_or("filings.recent not found".to_string())?,
+        None => root,
+    };
+
+    let filings = parse_filings(columns)?
+        .into_iter()
+        .filter(|filing| filter.matches(filing))
+        .collect();
+    let files = filings_object
+        .and_then(|filings_object| filings_object.get("files"))
+        .and_then(|files| files.as_array())
+        .map(|files| parse_files(files))
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(Submissions {
+        cik: get_string(root, "cik"),
+        name: get_string(root, "name"),
+        entity_type: get_string(root, "entityType"),
+        sic: get_string(root, "sic"),
+        sic_description: get_string(root, "sicDescription"),
+        tickers: get_strings(root, "tickers"),
+        exchanges: get_strings(root, "exchanges"),
+        fiscal_year_end: get_string(root, "fiscalYearEnd"),
+        state_of_incorporation: get_string(root, "stateOfIncorporation"),
+        filings,
+        files,
+    })
+}
+
+struct Columns<'a> {
+    accession_numbers: &'a Vec<Value>,
+    filing_dates: &'a Vec<Value>,
+    forms: &'a Vec<Value>,
+    report_dates: Option<&'a Vec<Value>>,
+    acceptance_date_times: Option<&'a Vec<Value>>,
+    acts: Option<&'a Vec<Value>>,
+    file_numbers: Option<&'a Vec<Value>>,
+    film_numbers: Option<&'a Vec<Value>>,
+    items: Option<&'a Vec<Value>>,
+    sizes: Option<&'a Vec<Value>>,
+    is_xbrls: Option<&'a Vec<Value>>,
+    is_inline_xbrls: Option<&'a Vec<Value>>,
+    primary_documents: Option<&'a Vec<Value>>,
+    primary_doc_descriptions: Option<&'a Vec<Value>>,
+}
+
+fn parse_filings(columns: &Map<String, Value>) -> Result<Vec<Filing>, String> {
+    let columns = Columns {
+        accession_numbers: get_column(columns, "accessionNumber")?
+            .ok_or("missing column: accessionNumber".to_string())?,
+        filing_dates: get_column(columns, "filingDate")?
+            .ok_or("missing column: filingDate".to_string())?,
+        forms: get_column(columns, "form")?.ok_or("missing column: form".to_string())?,
+        report_dates: get_column(columns, "reportDate")?,
+        acceptance_date_times: get_column(columns, "acceptanceDateTime")?,
+        acts: get_column(columns, "act")?,
+        file_numbers: get_column(columns, "fileNumber")?,
+        film_numbers: get_column(columns, "filmNumber")?,
+        items: get_column(columns, "items")?,
+        sizes: get_column(columns, "size")?,
+        is_xbrls: get_column(columns, "isXBRL")?,
+        is_inline_xbrls: get_column(columns, "isInlineXBRL")?,
+        primary_documents: get_column(columns, "primaryDocument")?,
+        primary_doc_descriptions: get_column(columns, "primaryDocDescription")?,
+    };
+
+    (0..columns.accession_numbers.len())
+        .map(|index| {
+            parse_filing(&columns, index).map_err(|e| format!("filing {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+fn parse_filing(columns: &Columns, index: usize) -> Result<Filing, String> {
+    let accession_number = value_to_string(columns.accession_numbers.get(index))
+        .ok_or("missing accessionNumber".to_string())?;
+    let filing_date = value_to_string(columns.filing_dates.get(index))
+        .map(|date| parse_date(&date, "filingDate"))
+        .transpose()?
+        .ok_or("missing filingDate".to_string())?;
+    let form = value_to_string(columns.forms.get(index)).ok_or("missing form".to_string())?;
+    let report_date = value_to_string(cell(columns.report_dates, index))
+        .map(|date| parse_date(&date, "reportDate"))
+        .transpose()?;
+    let acceptance_date_time = value_to_string(cell(columns.acceptance_date_times, index))
+        .map(|date_time| parse_date_time(&date_time, "acceptanceDateTime"))
+        .transpose()?;
+    let items = value_to_string(cell(columns.items, index))
+        .map(|items| {
+            items
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(Filing {
+        accession_number,
+        filing_date,
+        report_date,
+        acceptance_date_time,
+        act: value_to_string(cell(columns.acts, index)),
+        form,
+        file_number: value_to_string(cell(columns.file_numbers, index)),
+        film_number: value_to_string(cell(columns.film_numbers, index)),
+        items,
+        size: value_to_int64(cell(columns.sizes, index), "size")?,
+        is_xbrl: value_to_bool(cell(columns.is_xbrls, index)),
+        is_inline_xbrl: value_to_bool(cell(columns.is_inline_xbrls, index)),
+        primary_document: value_to_string(cell(columns.primary_documents, index)),
+        primary_doc_description: value_to_string(cell(columns.primary_doc_descriptions, index)),
+    })
+}
+
+fn parse_files(files: &[Value]) -> Result<Vec<SubmissionsFile>, String> {
+    files
+        .iter()
+        .enumerate()
+        .map(|(index, file)| {
+            let file = file
+                .as_object()
+                .ok_or(format!("file {}: expected a json object", index + 1))?;
+
+            Ok(SubmissionsFile {
+                name: get_string(file, "name")
+                    .ok_or(format!("file {}: missing name", index + 1))?,
+                filing_count: value_to_int64(file.get("filingCount"), "filingCount")
+                    .map_err(|e| format!("file {}: {}", index + 1, e))?,
+                filing_from: get_string(file, "filingFrom"),
+                filing_to: get_string(file, "filingTo"),
+            })
+        })
+        .collect()
+}
+
+fn cell(column: Option<&Vec<Value>>, index: usize) -> Option<&Value> {
+    column.and_then(|column| column.get(index))
+}
+
+fn get_column<'a>(
+    columns: &'a Map<String, Value>,
+    name: &str,
+) -> Result<Option<&'a Vec<Value>>, String> {
+    columns
+        .get(name)
+        .map(|column| {
+            column
+                .as_array()
+                .ok_or(format!("column {}: expected an array", name))
+        })
+        .transpose()
+}
+
+fn get_string(object: &Map<String, Value>, key: &str) -> Option<String> {
+    value_to_string(object.get(key))
+}
+
+fn get_strings(object: &Map<String, Value>, key: &str) -> Vec<String> {
+    object
+        .get(key)
+        .and_then(|value| value.as_array())
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|v| value_to_string(Some(v)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Empty strings are used for missing values in the column arrays.
+fn value_to_string(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn value_to_int64(value: Option<&Value>, field: &str) -> Result<Option<i64>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(n)) => n
+            .as_i64()
+            .map(Some)
+            .ok_or(format!("invalid {}: {}", field, n)),
+        Some(Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(Value::String(s)) => crate::xml::parse_int64(s.trim(), field).map(Some),
+        Some(other) => Err(format!("invalid {}: {}", field, other)),
+    }
+}
+
+fn value_to_bool(value: Option<&Value>) -> bool {
+    match value {
+        Some(Value::Bool(b)) => *b,
+        Some(Value::Number(n)) => n.as_i64() == Some(1),
+        Some(Value::String(s)) => matches!(s.trim(), "1" | "true"),
+        _ => false,
+    }
+}
+
+fn parse_date(date: &str, field: &str) -> Result<String, String> {
+    let bytes = date.as_bytes();
+    let valid = bytes.len() == 10
+        && bytes[4] == b'-'
+        && bytes[7] == b'-'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(i, b)| i == 4 || i == 7 || b.is_ascii_digit());
+
+    if valid {
+        Ok(date.to_string())
+    } else {
+        Err(format!("invalid {}: {}", field, date))
+    }
+}
+
+// e.g. 2024-05-02T18:04:25.000Z. The fractional seconds and the trailing Z
+// are optional.
+fn parse_date_time(date_time: &str, field: &str) -> Result<String, String> {
+    let invalid = || format!("invalid {}: {}", field, date_time);
+
+    let (date, time) = date_time.split_once('T').ok_or_else(invalid)?;
+    parse_date(date, field).map_err(|_| invalid())?;
+
+    let time = time.strip_suffix('Z').unwrap_or(time);
+    let (time, fraction) = time.split_once('.').unwrap_or((time, "0"));
+    let bytes = time.as_bytes();
+    let valid = bytes.len() == 8
+        && bytes[2] == b':'
+        && bytes[5] == b':'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(i, b)| i == 2 || i == 5 || b.is_ascii_digit())
+        && !fraction.is_empty()
+        && fraction.bytes().all(|b| b.is_ascii_digit());
+
+    if valid {
+        Ok(date_time.to_string())
+    } else {
+        Err(invalid())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::xml::{get_bool, get_int32, get_int64, get_ints, get_string};
 use roxmltree::Document as XMLDoc;
 use rustler::NifMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 // https://www.sec.gov/info/edgar/specifications/form13fxmltechspec
 
@@ -165,7 +166,7 @@ pub struct TableEntry {
     shares_or_print_amount: SharesOrPrintAmount,
     put_call: Option<String>,
     investment_discretion: String,
-    other_manager: Vec<i32>,
+    other_manager: Vec<i64>,
     voting_authority: VotingAuthority,
 }
 
@@ -177,9 +178,9 @@ pub struct SharesOrPrintAmount {
 
 #[derive(NifMap)]
 pub struct VotingAuthority {
-    sole: i32,
-    shared: i32,
-    none: i32,
+    sole: i64,
+    shared: i64,
+    none: i64,
 }
 
 #[rustler::nif]
@@ -514,18 +515,18 @@ fn parse_documents(node: &roxmltree::Node) -> Result<Vec<OtherDocument>, String>
         .filter(|node| node.has_tag_name("documents"))
         .flat_map(|node| node.children())
         .filter(|node| node.has_tag_name("document"))
-        .filter_map(|manager_node| {
+        .map(|manager_node| {
             let conformed_name = get_string(&manager_node, "conformedName").ok();
             let conformed_document_type = get_string(&manager_node, "conformedDocumentType").ok();
             let description = get_string(&manager_node, "description").ok();
             let contents = get_string(&manager_node, "contents").ok();
 
-            Some(OtherDocument {
+            OtherDocument {
                 conformed_name,
                 conformed_document_type,
                 description,
                 contents,
-            })
+            }
         })
         .collect();
     Ok(documents)
@@ -539,36 +540,41 @@ pub fn parse_form13f_table(xml: &str) -> Result<Table, String> {
     let entries = root_node
         .children()
         .filter(|root_node| root_node.has_tag_name("infoTable"))
-        .filter_map(|info_node| {
-            let name_of_issuer = get_string(&info_node, "nameOfIssuer").ok()?;
-            let title_of_class = get_string(&info_node, "titleOfClass").ok()?;
-            let cusip = get_string(&info_node, "cusip").ok()?;
-            let figi = get_string(&info_node, "figi").ok();
-            let value = get_int64(&info_node, "value").ok()?;
-            let shares_or_print_amount = parse_shares_or_print_amount(&info_node).ok()?;
-            let put_call = get_string(&info_node, "putCall").ok();
-            let investment_discretion = get_string(&info_node, "investmentDiscretion").ok()?;
-            let other_manager = get_ints(&info_node, "otherManager");
-            let voting_authority = parse_voting_authority(&info_node).ok()?;
-
-            Some(TableEntry {
-                name_of_issuer,
-                title_of_class,
-                cusip,
-                figi,
-                value,
-                shares_or_print_amount,
-                put_call,
-                investment_discretion,
-                other_manager,
-                voting_authority,
-            })
+        .enumerate()
+        .map(|(index, info_node)| {
+            parse_table_entry(&info_node).map_err(|e| format!("infoTable {}: {}", index + 1, e))
         })
-        .collect();
+        .collect::<Result<Vec<TableEntry>, String>>()?;
 
     Ok(Table { entries })
 }
 
+fn parse_table_entry(node: &roxmltree::Node) -> Result<TableEntry, String> {
+    let name_of_issuer = get_string(node, "nameOfIssuer")?;
+    let title_of_class = get_string(node, "titleOfClass")?;
+    let cusip = get_string(node, "cusip")?;
+    let figi = get_string(node, "figi").ok();
+    let value = get_int64(node, "value")?;
+    let shares_or_print_amount = parse_shares_or_print_amount(node)?;
+    let put_call = get_string(node, "putCall").ok();
+    let investment_discretion = get_string(node, "investmentDiscretion")?;
+    let other_manager = get_ints(node, "otherManager")?;
+    let voting_authority = parse_voting_authority(node)?;
+
+    Ok(TableEntry {
+        name_of_issuer,
+        title_of_class,
+        cusip,
+        figi,
+        value,
+        shares_or_print_amount,
+        put_call,
+        investment_discretion,
+        other_manager,
+        voting_authority,
+    })
+}
+
 fn parse_shares_or_print_amount(node: &roxmltree::Node) -> Result<SharesOrPrintAmount, String> {
     node.children()
         .find(|node| node.has_tag_name("shrsOrPrnAmt"))
@@ -590,10 +596,134 @@ fn parse_voting_authority(node: &roxmltree::Node) -> Result<VotingAuthority, Str
         .find(|node| node.has_tag_name("votingAuthority"))
         .ok_or("votingAuthority not found".to_string())
         .and_then(|voting_authority_node| {
-            let sole = get_int32(&voting_authority_node, "Sole")?;
-            let shared = get_int32(&voting_authority_node, "Shared")?;
-            let none = get_int32(&voting_authority_node, "None")?;
+            let sole = get_int64(&voting_authority_node, "Sole")?;
+            let shared = get_int64(&voting_authority_node, "Shared")?;
+            let none = get_int64(&voting_authority_node, "None")?;
 
             Ok(VotingAuthority { sole, shared, none })
         })
 }
+
+#[derive(NifMap)]
+pub struct Consistency {
+    is_consistent: bool,
+    entry_total: TotalCheck,
+    value_total: TotalCheck,
+    undeclared_other_managers: Vec<i64>,
+    duplicate_entries: Vec<DuplicateEntry>,
+}
+
+#[derive(NifMap)]
+pub struct TotalCheck {
+    declared: i64,
+    actual: i64,
+    matches: bool,
+}
+
+#[derive(NifMap)]
+pub struct DuplicateEntry {
+    name_of_issuer: String,
+    cusip: String,
+    indexes: Vec<usize>,
+}
+
+#[rustler::nif]
+pub fn check_form13f_consistency(document: Document, table: Table) -> Result<Consistency, String> {
+    let summary_page = document
+        .form_data
+        .summary_page
+        .ok_or("summaryPage not found".to_string())?;
+
+    let entry_total = TotalCheck::new(
+        summary_page.table_entry_total as i64,
+        table.entries.len() as i64,
+    );
+    let actual_value_total = table
+        .entries
+        .iter()
+        .try_fold(0i64, |total, entry| total.checked_add(entry.value))
+        .ok_or("int64 overflow in table value total".to_string())?;
+    let value_total = TotalCheck::new(summary_page.table_value_total, actual_value_total);
+
+    let declared_managers: HashSet<i64> = summary_page
+        .other_managers
+        .iter()
+        .filter_map(|manager| manager.sequence_number)
+        .map(|sequence_number| sequence_number as i64)
+        .collect();
+    let undeclared_other_managers: Vec<i64> = table
+        .entries
+        .iter()
+        .flat_map(|entry| entry.other_manager.iter().copied())
+        .filter(|sequence_number| !declared_managers.contains(sequence_number))
+        .collect::<BTreeSet<i64>>()
+        .into_iter()
+        .collect();
+
+    let duplicate_entries = find_duplicate_entries(&table.entries);
+
+    let is_consistent = entry_total.matches
+        && value_total.matches
+        && undeclared_other_managers.is_empty()
+        && duplicate_entries.is_empty();
+
+    Ok(Consistency {
+        is_consistent,
+        entry_total,
+        value_total,
+        undeclared_other_managers,
+        duplicate_entries,
+    })
+}
+
+impl TotalCheck {
+    fn new(declared: i64, actual: i64) -> Self {
+        TotalCheck {
+            declared,
+            actual,
+            matches: declared == actual,
+        }
+    }
+}
+
+fn find_duplicate_entries(entries: &[TableEntry]) -> Vec<DuplicateEntry> {
+    let mut indexes_by_key: HashMap<_, Vec<usize>> = HashMap::new();
+    let mut first_seen = Vec::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let key = (
+            entry.cusip.as_str(),
+            entry.title_of_class.as_str(),
+            entry.value,
+            entry.shares_or_print_amount.amount,
+            entry.shares_or_print_amount.shares_or_print_type.as_str(),
+            entry.put_call.as_deref(),
+            entry.investment_discretion.as_str(),
+            entry.other_manager.as_slice(),
+            entry.voting_authority.sole,
+            entry.voting_authority.shared,
+            entry.voting_authority.none,
+        );
+        let indexes = indexes_by_key.entry(key).or_default();
+        if indexes.is_empty() {
+            first_seen.push(key);
+        }
+        // 1-based, like the infoTable numbers of parse errors.
+        indexes.push(index + 1);
+    }
+
+    first_seen
+        .into_iter()
+        .filter_map(|key| indexes_by_key.remove(&key))
+        .filter(|indexes| indexes.len() > 1)
+        .map(|indexes| {
+            let entry = &entries[indexes[0] - 1];
+
+            DuplicateEntry {
+                name_of_issuer: entry.name_of_issuer.clone(),
+                cusip: entry.cusip.clone(),
+                indexes,
+            }
+        })
+        .collect()
+}
@@ -5,36 +5,93 @@ use std::collections::HashMap;
 
 #[derive(NifMap)]
 pub struct Document {
-    facts: Vec<Fact>,
+    pub(crate) facts: Vec<Fact>,
 }
 
-#[derive(NifMap)]
+#[derive(Clone, NifMap)]
 pub struct Fact {
-    context: Context,
-    concept: String,
-    value: Value,
+    pub(crate) context: Context,
+    pub(crate) concept: String,
+    pub(crate) value: Value,
     decimals: Option<String>,
-    unit: Option<String>,
+    pub(crate) unit: Option<String>,
+}
+
+impl Fact {
+    pub(crate) fn numeric_value(&self) -> Option<f64> {
+        match self.value {
+            Value::Int(value) => Some(value as f64),
+            Value::Float(value) => Some(value),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, NifMap)]
 pub struct Context {
-    entity: String,
-    segments: Vec<Segment>,
-    period: Period,
+    pub(crate) entity: String,
+    pub(crate) segments: Vec<Segment>,
+    pub(crate) period: Period,
 }
 
 #[derive(Clone, NifMap)]
 pub struct Segment {
-    dimension: String,
-    member: String,
+    pub(crate) dimension: String,
+    pub(crate) member: String,
 }
 
 #[derive(Clone, NifMap)]
 pub struct Period {
-    instant: Option<String>,
-    start_date: Option<String>,
-    end_date: Option<String>,
+    pub(crate) instant: Option<String>,
+    pub(crate) start_date: Option<String>,
+    pub(crate) end_date: Option<String>,
+}
+
+pub(crate) type PeriodKey = (Option<String>, Option<String>, Option<String>);
+
+impl Period {
+    pub(crate) fn key(&self) -> PeriodKey {
+        (
+            self.instant.clone(),
+            self.start_date.clone(),
+            self.end_date.clone(),
+        )
+    }
+}
+
+// The numeric facts without dimensions of a document keyed by concept and
+// period, for the standardized statements and the ratios. Periods are kept in
+// the order they first appear.
+pub(crate) struct FactIndex<'a> {
+    pub(crate) periods: Vec<&'a Period>,
+    facts: HashMap<(&'a str, PeriodKey), &'a Fact>,
+}
+
+impl<'a> FactIndex<'a> {
+    pub(crate) fn new(document: &'a Document) -> Self {
+        let mut periods: Vec<&Period> = vec![];
+        let mut facts = HashMap::new();
+
+        for fact in document
+            .facts
+            .iter()
+            .filter(|fact| fact.context.segments.is_empty() && fact.numeric_value().is_some())
+        {
+            let key = fact.context.period.key();
+            if !periods.iter().any(|period| period.key() == key) {
+                periods.push(&fact.context.period);
+            }
+            facts.entry((fact.concept.as_str(), key)).or_insert(fact);
+        }
+
+        FactIndex { periods, facts }
+    }
+
+    // Facts are keyed by local name, so accept prefixed concepts too.
+    pub(crate) fn get(&self, concept: &str, period: PeriodKey) -> Option<&'a Fact> {
+        let local_name = concept.rsplit(':').next().unwrap_or(concept);
+        self.facts.get(&(local_name, period)).copied()
+    }
 }
 
 #[rustler::nif]
@@ -50,7 +107,7 @@ pub fn parse_xbrl(xbrl: &str) -> Result<Document, String> {
         .unwrap_or_default();
 
     let units = parse_units(&root)?;
-    let contexts = parse_contexts(&root, &xbrldi_ns)?;
+    let contexts = parse_contexts(&root, xbrldi_ns)?;
 
     let facts: Vec<_> = root
         .children()
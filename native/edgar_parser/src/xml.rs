@@ -1,4 +1,12 @@
-#[derive(rustler::NifUntaggedEnum)]
+use std::num::{IntErrorKind, ParseIntError};
+
+#[derive(rustler::NifMap)]
+pub struct Filer {
+    cik: String,
+    ccc: Option<String>,
+}
+
+#[derive(Clone, PartialEq, rustler::NifUntaggedEnum)]
 pub enum Value {
     Int(i64),
     Float(f64),
@@ -16,14 +24,76 @@ pub fn get_string(node: &roxmltree::Node, tag: &str) -> Result<String, String> {
 
 pub fn get_int32(node: &roxmltree::Node, tag: &str) -> Result<i32, String> {
     let text = get_string(node, tag)?;
-    text.parse::<i32>()
-        .map_err(|_| format!("failed to parse int32 from tag: {}", tag))
+    text.trim().parse::<i32>().map_err(|e| {
+        if is_overflow(&e) {
+            format!("int32 overflow in tag: {}", tag)
+        } else {
+            format!("failed to parse int32 from tag: {}", tag)
+        }
+    })
 }
 
 pub fn get_int64(node: &roxmltree::Node, tag: &str) -> Result<i64, String> {
     let text = get_string(node, tag)?;
-    text.parse::<i64>()
-        .map_err(|_| format!("failed to parse int64 from tag: {}", tag))
+    parse_int64(&text, tag)
+}
+
+pub fn parse_int64(text: &str, tag: &str) -> Result<i64, String> {
+    text.trim().parse::<i64>().map_err(|e| {
+        if is_overflow(&e) {
+            format!("int64 overflow in tag: {}", tag)
+        } else {
+            format!("failed to parse int64 from tag: {}", tag)
+        }
+    })
+}
+
+pub fn get_optional_int64(node: &roxmltree::Node, tag: &str) -> Result<Option<i64>, String> {
+    node.children()
+        .find(|node| node.has_tag_name(tag))
+        .and_then(|node| node.text())
+        .map(|text| parse_int64(text, tag))
+        .transpose()
+}
+
+pub fn get_float64(node: &roxmltree::Node, tag: &str) -> Result<f64, String> {
+    let text = get_string(node, tag)?;
+    parse_float64(&text, tag)
+}
+
+pub fn get_optional_float64(node: &roxmltree::Node, tag: &str) -> Result<Option<f64>, String> {
+    node.children()
+        .find(|node| node.has_tag_name(tag))
+        .and_then(|node| node.text())
+        .map(|text| parse_float64(text, tag))
+        .transpose()
+}
+
+pub fn parse_float64(text: &str, tag: &str) -> Result<f64, String> {
+    text.trim()
+        .parse::<f64>()
+        .map_err(|_| format!("failed to parse float64 from tag: {}", tag))
+}
+
+// The filerCredentials of EDGAR XML submissions (Schedule 13D/G, Form 144,
+// N-MFP...).
+pub fn parse_filer(node: &roxmltree::Node) -> Result<Option<Filer>, String> {
+    node.descendants()
+        .find(|node| node.has_tag_name("filerCredentials"))
+        .map(|credentials_node| {
+            let cik = get_string(&credentials_node, "cik")?;
+            let ccc = get_string(&credentials_node, "ccc").ok();
+
+            Ok(Filer { cik, ccc })
+        })
+        .transpose()
+}
+
+fn is_overflow(e: &ParseIntError) -> bool {
+    matches!(
+        e.kind(),
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow
+    )
 }
 
 pub fn get_bool(node: &roxmltree::Node, tag: &str) -> Result<bool, String> {
@@ -35,11 +105,16 @@ pub fn get_bool(node: &roxmltree::Node, tag: &str) -> Result<bool, String> {
     }
 }
 
-pub fn get_ints(node: &roxmltree::Node, tag: &str) -> Vec<i32> {
+pub fn get_ints(node: &roxmltree::Node, tag: &str) -> Result<Vec<i64>, String> {
     node.children()
         .filter(|node| node.has_tag_name(tag))
         .filter_map(|node| node.text())
-        .flat_map(|text| text.split(',').filter_map(|s| s.trim().parse::<i32>().ok()))
+        .flat_map(|text| text.split(','))
+        .filter_map(|s| match s.trim().parse::<i64>() {
+            Ok(int_val) => Some(Ok(int_val)),
+            Err(e) if is_overflow(&e) => Some(Err(format!("int64 overflow in tag: {}", tag))),
+            Err(_) => None,
+        })
         .collect()
 }
 
@@ -301,6 +301,52 @@ defmodule EDGARTest do
     assert error == "resource not found"
   end
 
+  test "fact_table_from_file/1 flattens company facts into rows" do
+    {:ok, table} = EDGAR.fact_table_from_file("test/test_data/company_facts.json")
+
+    assert table.cik == 320_193
+    assert table.entity_name == "Apple Inc."
+    assert length(table.facts) == 7
+
+    revenue = Enum.find(table.facts, &(&1.concept == "Revenues"))
+    assert revenue.taxonomy == "us-gaap"
+    assert revenue.unit == "USD"
+    assert revenue.period_type == "duration"
+    assert revenue.start == "2022-09-25"
+    assert revenue.value == 383_285_000_000.0
+    assert revenue.fy == 2023
+    assert revenue.frame == "CY2023"
+
+    shares = Enum.find(table.facts, &(&1.concept == "EntityCommonStockSharesOutstanding"))
+    assert shares.period_type == "instant"
+    assert shares.start == nil
+  end
+
+  test "fact_table_from_file/2 filters concepts and dedupes by frame" do
+    {:ok, table} =
+      EDGAR.fact_table_from_file("test/test_data/company_facts.json", %{
+        concepts: ["us-gaap:Revenues", "AccountsPayableCurrent"],
+        dedupe_frames: true
+      })
+
+    assert Enum.map(table.facts, &{&1.concept, &1.frame}) == [
+             {"AccountsPayableCurrent", "CY2023Q3I"},
+             {"Revenues", "CY2023"},
+             {"Revenues", "CY2024Q1"}
+           ]
+  end
+
+  test "fact_table_from_file/1 flattens frames into rows" do
+    {:ok, table} = EDGAR.fact_table_from_file("test/test_data/frames.json")
+
+    [aar, abbott] = table.facts
+    assert aar.cik == 1750
+    assert aar.entity_name == "AAR CORP."
+    assert aar.concept == "AccountsPayableCurrent"
+    assert aar.frame == "CY2019Q1I"
+    assert abbott.value == 3_137_000_000.0
+  end
+
   test "filings/1 returns a filings list" do
     {:ok, filings} = EDGAR.filings("320193")
     assert is_list(filings)
@@ -323,6 +369,70 @@ defmodule EDGARTest do
     assert length(filings) == length(filings2) + 1
   end
 
+  test "filing_rows/2 returns typed 10-K filings" do
+    {:ok, filings} = EDGAR.filing_rows("320193", %{form_type: "10-K", limit: 1})
+    assert length(filings) == 1
+    assert hd(filings).form == "10-K"
+  end
+
+  test "submissions_from_file/1 returns row-oriented filings" do
+    {:ok, submissions} = EDGAR.submissions_from_file("test/test_data/submissions.json")
+
+    assert submissions.cik == "320193"
+    assert submissions.name == "Apple Inc."
+    assert submissions.tickers == ["AAPL"]
+    assert length(submissions.filings) == 4
+
+    [ten_q, eight_k, form4, _ten_k] = submissions.filings
+    assert ten_q.accession_number == "0000320193-24-000069"
+    assert ten_q.report_date == "2024-03-30"
+    assert ten_q.size == 5_224_631
+    assert ten_q.acceptance_date_time == "2024-05-02T18:04:25.000Z"
+    assert ten_q.is_xbrl
+    assert ten_q.items == []
+    assert eight_k.items == ["2.02", "9.01"]
+    assert form4.act == nil
+    refute form4.is_inline_xbrl
+
+    [file] = submissions.files
+    assert file.name == "CIK0000320193-submissions-001.json"
+    assert file.filing_count == 1213
+    assert file.filing_to == "2014-02-05"
+  end
+
+  test "submissions_from_file/2 filters by form type and date range" do
+    {:ok, submissions} =
+      EDGAR.submissions_from_file("test/test_data/submissions.json", %{
+        form_types: ["10-Q", "10-K", "4"],
+        date_from: ~D[2024-01-01],
+        date_to: "2024-04-30"
+      })
+
+    assert Enum.map(submissions.filings, & &1.form) == ["4"]
+  end
+
+  test "submissions_from_string/1 returns an error on an invalid acceptance date time" do
+    json =
+      "test/test_data/submissions.json"
+      |> File.read!()
+      |> String.replace("2024-05-02T18:04:25.000Z", "2024-05-02 18:04")
+
+    {:error, error} = EDGAR.submissions_from_string(json)
+    assert error == "filing 1: invalid acceptanceDateTime: 2024-05-02 18:04"
+  end
+
+  test "submissions_from_file/1 parses a paging file" do
+    {:ok, page} = EDGAR.submissions_from_file("test/test_data/submissions_page.json")
+
+    assert page.cik == nil
+    assert page.files == []
+
+    [schedule13, form4] = page.filings
+    assert schedule13.form == "SC 13G/A"
+    assert schedule13.report_date == nil
+    assert form4.report_date == "2014-01-31"
+  end
+
   test "filings/1 returns an error if no filings" do
     {:error, error} = EDGAR.filings("0")
     assert error == "resource not found"
@@ -403,6 +513,270 @@ defmodule EDGARTest do
     assert filing.document_type == "4/A"
   end
 
+  test "form4_from_file/2 keeps every footnote id of a value" do
+    {:ok, filing} = EDGAR.form4_from_file("test/test_data/doc4.xml")
+
+    [transaction | _] = filing.non_derivative_table.transactions
+
+    assert transaction.transaction_coding.footnote_ids == ["F1", "F2", "F3"]
+    assert transaction.transaction_coding.footnotes == []
+  end
+
+  test "form4_from_file/2 resolves footnote texts when requested" do
+    {:ok, filing} =
+      EDGAR.form4_from_file("test/test_data/doc4_sales.xml", %{resolve_footnotes: true})
+
+    sale =
+      Enum.find(
+        filing.non_derivative_table.transactions,
+        &(&1.transaction_coding.transaction_code == "S")
+      )
+
+    price = sale.transaction_amounts.price_per_share
+
+    assert price.footnote_ids == ["F4"]
+    assert [note] = price.footnotes
+    assert note =~ "prices ranging from $139.50 to $140.75"
+  end
+
+  test "form4_from_file/1 returns every reporting owner of a joint filing" do
+    {:ok, filing} = EDGAR.form4_from_file("test/test_data/doc4.xml")
+
+    assert Enum.map(filing.reporting_owners, & &1.id.cik) == ["0000343434", "1212121212"]
+    assert filing.reporting_owner == hd(filing.reporting_owners)
+
+    [owner, joint_owner] = filing.reporting_owners
+    assert owner.id.ccc == "a#ofc0rn"
+    assert owner.address.zip_code == "21212"
+    assert owner.relationship.officer_title == "President and CEO"
+    assert joint_owner.address == nil
+  end
+
+  test "form4_from_file/1 parses the transaction timeliness" do
+    {:ok, filing} = EDGAR.form4_from_file("test/test_data/doc4.xml")
+
+    [transaction | _] = filing.non_derivative_table.transactions
+    assert transaction.transaction_timeliness.footnote_ids == ["F3"]
+  end
+
+  test "form4_from_file/1 returns every owner signature" do
+    {:ok, filing} = EDGAR.form4_from_file("test/test_data/doc4.xml")
+
+    assert filing.owner_signatures == [
+             %{name: "Jane Doe", date: "2002-11-23"},
+             %{name: "John Q. Public", date: "2002-11-22"},
+             %{name: "Uncle Sam", date: "2002-11-24"}
+           ]
+
+    assert filing.warnings == []
+  end
+
+  test "form4_from_string/1 warns when the owner signature is missing" do
+    xml =
+      "test/test_data/doc4_sales.xml"
+      |> File.read!()
+      |> String.replace(~r/<ownerSignature>.*<\/ownerSignature>/s, "")
+
+    {:ok, filing} = EDGAR.form4_from_string(xml)
+
+    assert filing.owner_signatures == []
+    assert filing.warnings == ["ownerSignature not found"]
+  end
+
+  test "form4_from_file/1 tolerates fields missing from legacy schema versions" do
+    {:ok, filing} = EDGAR.form4_from_file("test/test_data/doc4_x0101.xml")
+
+    assert filing.schema_version == "X0101"
+    assert filing.period_of_report == "2003-07-01"
+    assert filing.issuer.trading_symbol == nil
+    assert filing.warnings == ["ownerSignature not found"]
+    assert length(filing.non_derivative_table.transactions) == 1
+
+    {:ok, filing} =
+      "test/test_data/doc4_x0101.xml"
+      |> File.read!()
+      |> String.replace("<periodOfReport>2003-07-01</periodOfReport>", "")
+      |> EDGAR.form4_from_string()
+
+    assert filing.period_of_report == nil
+  end
+
+  test "form4_from_file/1 parses a legacy form wrapped in the submission text" do
+    {:ok, filing} = EDGAR.form4_from_file("test/test_data/doc4_x0202.txt")
+
+    assert filing.schema_version == "X0202"
+    assert filing.issuer.trading_symbol == "LGCY"
+    assert filing.owner_signatures == [%{name: "Richard Doe", date: "2004-02-13"}]
+  end
+
+  test "form4_from_string/1 requires the trading symbol on current schema versions" do
+    xml =
+      "test/test_data/doc4_sales.xml"
+      |> File.read!()
+      |> String.replace("<issuerTradingSymbol>EXMP</issuerTradingSymbol>", "")
+
+    assert EDGAR.form4_from_string(xml) == {:error, "missing tag: issuerTradingSymbol"}
+  end
+
+  test "merge_ownership_amendments/1 replaces amended transactions" do
+    {:ok, original} = EDGAR.form4_from_file("test/test_data/doc4.xml")
+    {:ok, amendment} = EDGAR.form4_from_file("test/test_data/doc4a_partial.xml")
+
+    {:ok, merged} = EDGAR.merge_ownership_amendments([amendment, original])
+
+    [amended, kept, added] = merged.non_derivative_transactions
+    assert amended.status == "amended"
+    assert amended.document_type == "4/A"
+    assert amended.transaction.transaction_amounts.shares.value == 2500
+
+    assert amended.changed_fields == [
+             "transaction_amounts.shares",
+             "post_transaction_amounts.shares_owned_following_transaction"
+           ]
+
+    assert kept.status == "original"
+    assert kept.document_type == "4"
+    assert kept.transaction.transaction_coding.transaction_code == "S"
+
+    assert added.status == "added"
+    assert added.transaction.transaction_coding.transaction_code == "P"
+
+    [derivative] = merged.derivative_transactions
+    assert derivative.status == "original"
+  end
+
+  test "merge_ownership_amendments/1 keeps unchanged restatements as original" do
+    {:ok, original} = EDGAR.form4_from_file("test/test_data/doc4.xml")
+    {:ok, amendment} = EDGAR.form4_from_file("test/test_data/doc4a.xml")
+
+    {:ok, merged} = EDGAR.merge_ownership_amendments([original, amendment])
+
+    assert Enum.map(merged.non_derivative_transactions, & &1.status) == ["original", "original"]
+    assert Enum.all?(merged.non_derivative_transactions, &(&1.changed_fields == []))
+  end
+
+  test "merge_ownership_amendments/1 matches a corrected transaction date by position" do
+    {:ok, original} = EDGAR.form4_from_file("test/test_data/doc4.xml")
+
+    {:ok, amendment} =
+      "test/test_data/doc4a.xml"
+      |> File.read!()
+      |> String.replace("<value>2002-11-01</value>", "<value>2002-11-04</value>")
+      |> EDGAR.form4_from_string(%{resolve_footnotes: true})
+
+    {:ok, merged} = EDGAR.merge_ownership_amendments([original, amendment])
+
+    [amended, kept] = merged.non_derivative_transactions
+    assert amended.status == "amended"
+    assert amended.changed_fields == ["transaction_date"]
+    assert amended.transaction.transaction_date.value == "2002-11-04"
+    assert kept.status == "original"
+    assert kept.changed_fields == []
+  end
+
+  test "merge_ownership_amendments/1 returns an error for different owners" do
+    {:ok, original} = EDGAR.form4_from_file("test/test_data/doc4.xml")
+    other = put_in(original.issuer.cik, "0000320193")
+
+    {:error, error} = EDGAR.merge_ownership_amendments([original, other])
+    assert error == "documents must have the same issuer and reporting owner"
+  end
+
+  test "insider_transactions/1 normalizes transaction codes and amounts" do
+    {:ok, form} = EDGAR.form4_from_file("test/test_data/doc4_sales.xml")
+    transactions = EDGAR.insider_transactions(form)
+
+    assert Enum.map(transactions, & &1.transaction_code) == ["M", "F", "S", "G", "P", "M"]
+
+    [exercise, withholding, sale, gift, purchase, units] = transactions
+    assert exercise.share_change == 50_000
+    refute exercise.is_open_market
+
+    assert withholding.share_change == -20_000
+    assert withholding.value == 2_764_000
+
+    assert sale.transaction_description == "Open market or private sale"
+    assert sale.issuer_trading_symbol == "EXMP"
+    assert sale.reporting_owner_name == "SMITH JOHN"
+    assert sale.share_change == -10_000
+    assert sale.value == 1_401_234
+    assert sale.is_open_market
+    assert sale.is_10b5_1_plan
+    assert sale.price_range_low == 139.5
+    assert sale.price_range_high == 140.75
+    assert sale.plan_adoption_date == "2022-05-11"
+
+    assert gift.transaction_description == "Bona fide gift"
+    refute gift.is_10b5_1_plan
+
+    assert purchase.is_open_market
+    assert purchase.direct_or_indirect_ownership == "I"
+    assert purchase.nature_of_ownership == "By Trust"
+    assert purchase.trust_name == "Smith Family Trust"
+    assert purchase.indirect_ownership_description =~ "held by the Smith Family Trust"
+    assert purchase.price_range_low == nil
+
+    assert units.is_derivative
+    assert units.share_change == -50_000
+    assert units.underlying_security_title == "Common Stock"
+    assert units.underlying_security_shares == 50_000
+  end
+
+  test "insider_transactions/1 parses price ranges from footnotes with non-ascii text" do
+    xml =
+      "test/test_data/doc4_sales.xml"
+      |> File.read!()
+      |> String.replace("This transaction was executed", "İİİ This transaction was executed")
+
+    {:ok, form} = EDGAR.form4_from_string(xml)
+    sale = Enum.find(EDGAR.insider_transactions(form), &(&1.transaction_code == "S"))
+
+    assert sale.price_range_low == 139.5
+    assert sale.price_range_high == 140.75
+  end
+
+  test "insider_transactions/1 does not take a price range from words containing rang" do
+    note =
+      "The sales were made under a trading arrangement. The price reported above is a " <>
+        "weighted average price of $140.12, for shares sold at prices ranging from " <>
+        "$139.50 to $140.75, inclusive."
+
+    xml =
+      "test/test_data/doc4_sales.xml"
+      |> File.read!()
+      |> String.replace(~r/<footnote id="F4">[^<]*</, "<footnote id=\"F4\">#{note}<")
+
+    {:ok, form} = EDGAR.form4_from_string(xml)
+    sale = Enum.find(EDGAR.insider_transactions(form), &(&1.transaction_code == "S"))
+
+    assert sale.price_range_low == 139.5
+    assert sale.price_range_high == 140.75
+  end
+
+  test "insider_transactions/1 flags open market trades from the aff10b5One checkbox" do
+    {:ok, form} = EDGAR.form4_from_file("test/test_data/doc4.xml")
+    transactions = EDGAR.insider_transactions(form)
+
+    assert Enum.map(transactions, &{&1.transaction_code, &1.is_10b5_1_plan}) == [
+             {"J", false},
+             {"S", true},
+             {"C", false}
+           ]
+  end
+
+  test "insider_transactions/1 ignores footnotes saying a trade was not under a 10b5-1 plan" do
+    xml =
+      "test/test_data/doc4_sales.xml"
+      |> File.read!()
+      |> String.replace("were effected pursuant to", "were not effected pursuant to")
+
+    {:ok, form} = EDGAR.form4_from_string(xml)
+    sale = Enum.find(EDGAR.insider_transactions(form), &(&1.transaction_code == "S"))
+
+    refute sale.is_10b5_1_plan
+    assert sale.plan_adoption_date == nil
+  end
+
   test "form4_from_url/1 returns a parsed form 4 filing" do
     {:ok, filing} =
       EDGAR.form4_from_url(
@@ -529,11 +903,728 @@ defmodule EDGARTest do
     assert length(table.entries) > 0
   end
 
+  test "form13f_table_from_file/1 parses voting authority above 32 bits" do
+    {:ok, table} = EDGAR.form13f_table_from_file("test/test_data/doc13f_table.xml")
+
+    [entry | _] = table.entries
+    assert entry.voting_authority.sole == 8_488_745_632_154_587
+    assert entry.other_manager == [1, 2, 3]
+  end
+
+  test "form13f_table_from_string/1 returns an error on int64 overflow" do
+    xml = """
+    <informationTable>
+      <infoTable>
+        <nameOfIssuer>3M CO</nameOfIssuer>
+        <titleOfClass>COM</titleOfClass>
+        <cusip>88579Y101</cusip>
+        <value>17594</value>
+        <shrsOrPrnAmt>
+          <sshPrnamt>190371</sshPrnamt>
+          <sshPrnamtType>SH</sshPrnamtType>
+        </shrsOrPrnAmt>
+        <investmentDiscretion>SOLE</investmentDiscretion>
+        <votingAuthority>
+          <Sole>99999999999999999999</Sole>
+          <Shared>0</Shared>
+          <None>0</None>
+        </votingAuthority>
+      </infoTable>
+    </informationTable>
+    """
+
+    {:error, error} = EDGAR.form13f_table_from_string(xml)
+    assert error == "infoTable 1: int64 overflow in tag: Sole"
+  end
+
   test "form13f_table_from_file/1 returns an error if no file" do
     {:error, error} = EDGAR.form13f_table_from_file("test/test_data/invalid.xml")
     assert error == :enoent
   end
 
+  test "form13f_consistency/2 reports mismatches between summary page and table" do
+    {:ok, document} = EDGAR.form13f_document_from_file("test/test_data/doc13f_hr.xml")
+    {:ok, table} = EDGAR.form13f_table_from_file("test/test_data/doc13f_table.xml")
+    {:ok, consistency} = EDGAR.form13f_consistency(document, table)
+
+    refute consistency.is_consistent
+    assert consistency.entry_total.declared == 0
+    assert consistency.entry_total.actual == length(table.entries)
+    assert consistency.undeclared_other_managers == [1, 2, 3, 4, 5]
+  end
+
+  test "form13f_consistency/2 accepts a table matching its summary page" do
+    {:ok, document} = EDGAR.form13f_document_from_file("test/test_data/doc13f_hr_summary.xml")
+    {:ok, table} = EDGAR.form13f_table_from_file("test/test_data/doc13f_table.xml")
+    {:ok, consistency} = EDGAR.form13f_consistency(document, table)
+
+    assert consistency.is_consistent == true
+    assert consistency.entry_total == %{declared: 5, actual: 5, matches: true}
+    assert consistency.value_total == %{declared: 49370, actual: 49370, matches: true}
+    assert consistency.undeclared_other_managers == []
+    assert consistency.duplicate_entries == []
+  end
+
+  test "form13f_consistency/2 reports duplicated table rows" do
+    {:ok, document} = EDGAR.form13f_document_from_file("test/test_data/doc13f_hr_summary.xml")
+    {:ok, table} = EDGAR.form13f_table_from_file("test/test_data/doc13f_table_duplicate.xml")
+    {:ok, consistency} = EDGAR.form13f_consistency(document, table)
+
+    refute consistency.is_consistent
+    assert consistency.entry_total == %{declared: 5, actual: 6, matches: false}
+    assert consistency.undeclared_other_managers == []
+
+    assert consistency.duplicate_entries == [
+             %{name_of_issuer: "ABBOTT LABS", cusip: "002824100", indexes: [5, 6]}
+           ]
+  end
+
+  test "form13f_consistency/2 returns an error when the table value total overflows" do
+    {:ok, document} = EDGAR.form13f_document_from_file("test/test_data/doc13f_hr_summary.xml")
+
+    xml =
+      "test/test_data/doc13f_table.xml"
+      |> File.read!()
+      |> String.replace("<value>279</value>", "<value>9223372036854775807</value>")
+
+    {:ok, table} = EDGAR.form13f_table_from_string(xml)
+
+    {:error, error} = EDGAR.form13f_consistency(document, table)
+    assert error == "int64 overflow in table value total"
+  end
+
+  test "form13f_consistency/2 returns an error without a summary page" do
+    {:ok, document} = EDGAR.form13f_document_from_file("test/test_data/doc13f_nt.xml")
+    {:ok, table} = EDGAR.form13f_table_from_file("test/test_data/doc13f_table.xml")
+
+    {:error, error} = EDGAR.form13f_consistency(document, table)
+    assert error == "summaryPage not found"
+  end
+
+  test "adv_feed_from_file/1 returns firms keyed by CRD number" do
+    {:ok, feed} = EDGAR.adv_feed_from_file("test/test_data/adv_feed.xml")
+
+    assert feed.generated_on == "2024-07-01"
+
+    [firm, small_firm] = feed.firms
+    assert firm.crd_number == "105958"
+    assert firm.sec_number == "801-11953"
+    assert firm.regulatory_aum.total == 1_285_000_000_000.0
+    assert firm.regulatory_aum.total_accounts == 2300
+    assert firm.number_of_clients_over_100 == 2300
+    assert firm.custody.client_securities
+    assert firm.has_disciplinary_disclosures
+
+    assert Enum.filter(firm.disciplinary_flags, & &1.answer) == [
+             %{question: "Q11C1", answer: true}
+           ]
+
+    assert Enum.map(firm.direct_owners, & &1.name) == ["SMITH, JORDAN", "EXAMPLE HOLDINGS LLC"]
+    assert Enum.map(firm.indirect_owners, & &1.name) == ["EXAMPLE FAMILY TRUST"]
+
+    assert small_firm.has_disciplinary_disclosures == false
+    assert small_firm.custody == nil
+  end
+
+  test "form144_from_file/1 returns a parsed form 144 document" do
+    {:ok, document} = EDGAR.form144_from_file("test/test_data/form144.xml")
+
+    assert document.submission_type == "144"
+    assert document.issuer.cik == "0000320193"
+    assert document.seller.name == "Jordan Example"
+    assert document.seller.relationships_to_issuer == ["Officer"]
+
+    [securities_information] = document.securities_information
+    assert securities_information.broker.name == "Example Securities LLC"
+    assert securities_information.units_to_be_sold == 59162.0
+    assert securities_information.aggregate_market_value == 10_472_865.24
+    assert securities_information.approx_sale_date == "10/02/2023"
+    assert securities_information.securities_exchange_name == "NASDAQ"
+
+    [to_be_sold] = document.securities_to_be_sold
+    assert to_be_sold.nature_of_acquisition_transaction == "Restricted Stock Vesting"
+
+    [sold] = document.securities_sold_in_past_3_months
+    assert sold.gross_proceeds == 2_125_440.0
+    assert document.notice_signature.plan_adoption_dates == ["05/12/2023"]
+  end
+
+  test "formc_from_file/1 returns a parsed form C document" do
+    {:ok, document} = EDGAR.formc_from_file("test/test_data/formc.xml")
+
+    assert document.header_data.submission_type == "C"
+
+    issuer_information = document.form_data.issuer_information
+    assert issuer_information.issuer.name == "Example Brewing Co."
+    assert issuer_information.intermediary.crd_number == "283503"
+
+    offering = document.form_data.offering_information
+    assert offering.target_offering_amount == 25_000.0
+    assert offering.maximum_offering_amount == 618_000.0
+    assert offering.deadline_date == "12-31-2024"
+    assert offering.security_offered_type == "Other"
+
+    disclosure = document.form_data.annual_report_disclosure
+    assert disclosure.current_employees == 12
+    assert disclosure.most_recent_fiscal_year.revenue == 1_320_000.0
+    assert disclosure.prior_fiscal_year.net_income == -42_000.0
+
+    assert length(document.form_data.signature_info.signature_persons) == 2
+  end
+
+  test "formc_from_string/1 returns an error on a malformed employee count" do
+    xml =
+      "test/test_data/formc.xml"
+      |> File.read!()
+      |> String.replace("<currentEmployees>12<", "<currentEmployees>twelve<")
+
+    {:error, error} = EDGAR.formc_from_string(xml)
+    assert error == "failed to parse int64 from tag: currentEmployees"
+  end
+
+  test "formd_from_file/1 returns a parsed form D document" do
+    {:ok, document} = EDGAR.formd_from_file("test/test_data/formd.xml")
+
+    assert document.submission_type == "D"
+    assert document.primary_issuer.entity_name == "Example Ventures Fund II, L.P."
+    assert document.offering_data.federal_exemptions_exclusions == ["06b", "3C", "3C.1"]
+
+    amounts = document.offering_data.offering_sales_amounts
+    assert amounts.is_total_offering_amount_indefinite
+    assert amounts.total_amount_sold == 42_500_000
+  end
+
+  test "formd_from_string/1 returns an error on a malformed amount" do
+    xml =
+      "test/test_data/formd.xml"
+      |> File.read!()
+      |> String.replace("<totalAmountSold>42500000<", "<totalAmountSold>$42.5M<")
+
+    {:error, error} = EDGAR.formd_from_string(xml)
+    assert error == "failed to parse int64 from tag: totalAmountSold"
+  end
+
+  test "formd_from_file/1 returns an error if no file" do
+    {:error, error} = EDGAR.formd_from_file("test/test_data/invalid.xml")
+    assert error == :enoent
+  end
+
+  test "npx_document_from_file/1 returns a parsed form N-PX document" do
+    {:ok, document} = EDGAR.npx_document_from_file("test/test_data/npx.xml")
+
+    assert document.header_data.submission_type == "N-PX"
+    assert document.header_data.filer_info.period_of_report == "06/30/2024"
+    assert document.form_data.cover_page.report_type == "FUND VOTING REPORT"
+
+    [series] = document.form_data.series
+    assert series.id == "S000002277"
+  end
+
+  test "npx_table_from_file/1 returns every proxy vote record" do
+    {:ok, table} = EDGAR.npx_table_from_file("test/test_data/npx_table.xml")
+
+    [director_vote, proposal_vote] = table.entries
+    assert director_vote.cusip == "037833100"
+    assert director_vote.meeting_date == "02/28/2024"
+    assert director_vote.vote_categories == ["DIRECTOR ELECTIONS"]
+    assert director_vote.other_managers == [1]
+
+    assert Enum.map(proposal_vote.votes, & &1.how_voted) == ["AGAINST", "ABSTAIN"]
+    assert Enum.map(proposal_vote.votes, & &1.shares_voted) == [1_200_000.0, 50_000.0]
+    assert Enum.all?(proposal_vote.votes, &(&1.management_recommendation == "AGAINST"))
+  end
+
+  test "nmfp_from_file/1 returns a parsed form N-MFP document" do
+    {:ok, document} = EDGAR.nmfp_from_file("test/test_data/nmfp.xml")
+
+    assert document.submission_type == "N-MFP3"
+
+    series = document.series_level_info
+    assert series.weighted_average_maturity == 28
+    assert series.weighted_average_life == 91
+    assert series.net_assets == 25_464_250_000.0
+    assert Enum.map(series.seven_day_gross_yields, & &1.value) == [0.0541, 0.0542]
+
+    [weekly] = series.liquidity.percentage_weekly_liquid_assets
+    assert weekly.date == "2024-06-28"
+    assert weekly.value == 0.5577
+
+    [treasury, repo] = document.schedule_of_portfolio_securities.entries
+    assert treasury.cusip == "912797GK7"
+    assert treasury.percentage_of_net_assets == 0.0196
+    assert repo.is_daily_liquid_asset
+  end
+
+  test "nmfp_from_string/1 keeps the friday labels of N-MFP2 weekly values" do
+    weekly = """
+    <totalValueDailyLiquidAssets>
+      <totalValueDailyLiquidAssetsFridayDay1>9850000000.00</totalValueDailyLiquidAssetsFridayDay1>
+      <totalValueDailyLiquidAssetsFridayDay3>9900000000.00</totalValueDailyLiquidAssetsFridayDay3>
+    </totalValueDailyLiquidAssets>
+    """
+
+    xml =
+      "test/test_data/nmfp.xml"
+      |> File.read!()
+      |> String.replace(
+        ~r/<totalValueDailyLiquidAssets>.*<\/totalValueDailyLiquidAssets>/s,
+        weekly
+      )
+
+    {:ok, document} = EDGAR.nmfp_from_string(xml)
+
+    [day1, day3] = document.series_level_info.liquidity.daily_liquid_assets
+    assert day1.date == nil
+    assert day1.label == "FridayDay1"
+    assert day1.value == 9_850_000_000.0
+    assert day3.label == "FridayDay3"
+
+    [weekly] = document.series_level_info.liquidity.percentage_weekly_liquid_assets
+    assert weekly.label == nil
+  end
+
+  test "nport_from_file/1 returns a parsed form N-PORT document" do
+    {:ok, document} = EDGAR.nport_from_file("test/test_data/nport.xml")
+
+    assert document.header_data.submission_type == "NPORT-P"
+    assert document.form_data.general_info.registrant_name == "VANGUARD INDEX FUNDS"
+    assert length(document.form_data.investments) == 3
+
+    [equity, debt, future] = document.form_data.investments
+    assert equity.identifiers.ticker == "AAPL"
+    assert debt.debt_security.maturity_date == "2023-11-16"
+    assert future.derivative.derivative_category == "FUT"
+  end
+
+  test "nport_from_string/1 returns an error on a malformed optional number" do
+    xml =
+      "test/test_data/nport.xml"
+      |> File.read!()
+      |> String.replace("<valUSD>29607420999.34</valUSD>", "<valUSD>N/A</valUSD>")
+
+    {:error, error} = EDGAR.nport_from_string(xml)
+    assert error == "invstOrSec 1: failed to parse float64 from tag: valUSD"
+  end
+
+  test "nport_from_file/1 returns an error if no file" do
+    {:error, error} = EDGAR.nport_from_file("test/test_data/invalid.xml")
+    assert error == :enoent
+  end
+
+  test "schedule13_from_file/1 returns a parsed schedule 13D/A document" do
+    {:ok, document} = EDGAR.schedule13_from_file("test/test_data/schedule13d.xml")
+
+    assert document.submission_type == "SCHEDULE 13D/A"
+    assert document.cover_page.issuer.cusip == "191216100"
+    assert length(document.reporting_persons) == 2
+
+    [_, person] = document.reporting_persons
+    assert person.percent_of_class == 7.4
+    assert person.type_of_reporting_person == ["IN", "HC"]
+  end
+
+  test "schedule13_from_file/1 returns a parsed schedule 13G document" do
+    {:ok, document} = EDGAR.schedule13_from_file("test/test_data/schedule13g.xml")
+
+    assert document.submission_type == "SCHEDULE 13G"
+    assert document.cover_page.designated_rules == ["Rule 13d-1(b)"]
+
+    [person] = document.reporting_persons
+    assert person.sole_dispositive_power == 1_268_374_520.0
+    assert person.percent_of_class == 8.76
+  end
+
+  test "filing_index_from_file/1 returns the header and typed documents" do
+    {:ok, index} = EDGAR.filing_index_from_file("test/test_data/filing_index.htm")
+
+    assert index.header.accession_number == "0000320193-24-000061"
+    assert index.header.form_type == "4"
+    assert index.header.filing_date == "2024-04-03"
+    assert index.header.accepted == "2024-04-03 18:30:41"
+    assert index.header.document_count == 1
+
+    [owner, issuer] = index.header.entities
+    assert owner.role == "Reporting"
+    assert owner.cik == "0001214156"
+    assert issuer.name == "Apple Inc."
+
+    assert length(index.documents) == 4
+
+    document = EDGAR.find_index_document(index, ["4", "4/A"])
+    assert document.name == "wk-form4_1712183435.xml"
+    assert document.sequence == 1
+    assert document.size == 4721
+
+    exhibit = Enum.find(index.documents, &(&1.document_type == "EX-99.1"))
+    assert exhibit.name == "wk-ex991_1712183435.htm"
+
+    assert exhibit.href ==
+             "/Archives/edgar/data/320193/000032019324000061/wk-ex991_1712183435.htm"
+  end
+
+  test "find_index_document/2 selects the N-PX vote table by its declared type" do
+    row = fn name, type ->
+      "<tr><td>1</td><td></td><td>#{name}</td><td>#{type}</td><td>1</td></tr>"
+    end
+
+    html = """
+    <table class="tableFile">
+    #{row.("primary_doc.html", "N-PX")}
+    #{row.("primary_doc.xml", "N-PX")}
+    #{row.("series_report.xml", "EX-99")}
+    #{row.("vote_table.xml", "PROXY VOTING RECORD")}
+    </table>
+    """
+
+    {:ok, index} = EDGAR.filing_index_from_string(html)
+
+    assert EDGAR.find_index_document(index, ["N-PX", "N-PX/A"]).name == "primary_doc.xml"
+    assert EDGAR.find_index_document(index, ["PROXY VOTING RECORD"]).name == "vote_table.xml"
+  end
+
+  test "filing_index_headers_from_file/1 returns the header and typed documents" do
+    {:ok, index} =
+      EDGAR.filing_index_headers_from_file("test/test_data/filing_index_headers.html")
+
+    assert index.header.accession_number == "0000320193-24-000061"
+    assert index.header.form_type == "4"
+    assert index.header.filing_date == "2024-04-03"
+    assert index.header.accepted == "2024-04-03 18:30:41"
+    assert index.header.period_of_report == "2024-04-01"
+
+    [owner, issuer] = index.header.entities
+    assert owner.role == "Reporting"
+    assert owner.name == "COOK TIMOTHY D"
+    assert issuer.role == "Issuer"
+    assert issuer.cik == "0000320193"
+
+    [document] = index.documents
+    assert document.document_type == "4"
+    assert document.name == "wk-form4_1712183435.xml"
+    assert document.description == "FORM 4"
+  end
+
+  test "filing_summary_from_file/1 returns report metadata and input files" do
+    {:ok, summary} = EDGAR.filing_summary_from_file("test/test_data/filing_summary.xml")
+
+    assert summary.report_type == "8-K"
+
+    [cover, all_reports] = summary.reports
+    assert cover.html_file_name == "R1.htm"
+    assert cover.short_name == "Document and Entity Information"
+    assert cover.menu_category == "Cover"
+    assert cover.position == 1
+    assert all_reports.role == nil
+
+    [primary | _] = summary.input_files
+    assert primary.name == "d501487d8k.htm"
+    assert primary.doc_type == "8-K"
+  end
+
+  test "xbrl_report_facts/3 groups xbrl facts under their report" do
+    {:ok, summary} = EDGAR.filing_summary_from_file("test/test_data/filing_summary.xml")
+    {:ok, presentation} = EDGAR.presentation_linkbase_from_file("test/test_data/xbrl_pre.xml")
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl.xml")
+
+    [cover] = EDGAR.xbrl_report_facts(summary, presentation, xbrl)
+
+    assert cover.report.short_name == "Document and Entity Information"
+
+    assert cover.concepts == [
+             "CoverAbstract",
+             "DocumentType",
+             "EntityRegistrantName",
+             "TradingSymbol"
+           ]
+
+    assert length(cover.facts) == 2
+    assert Enum.any?(cover.facts, &(&1.concept == "DocumentType" and &1.value == "8-K"))
+    refute Enum.any?(cover.facts, &(&1.concept == "TradingSymbol"))
+  end
+
+  test "xbrl_report_facts/3 keeps dimensional facts whose axis and member are presented" do
+    {:ok, summary} = EDGAR.filing_summary_from_file("test/test_data/filing_summary.xml")
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl.xml")
+
+    locators =
+      ["us-gaap_StatementClassOfStockAxis", "brka_ClassACommonStockMember"]
+      |> Enum.map_join(fn id ->
+        ~s(<link:loc xlink:type="locator" xlink:href="schema.xsd##{id}" xlink:label="#{id}"/>)
+      end)
+
+    {:ok, presentation} =
+      "test/test_data/xbrl_pre.xml"
+      |> File.read!()
+      |> String.replace("</link:presentationLink>", locators <> "</link:presentationLink>")
+      |> EDGAR.presentation_linkbase_from_string()
+
+    [role] = presentation
+    assert role.dimensions == ["StatementClassOfStockAxis"]
+    assert role.members == ["ClassACommonStockMember"]
+
+    [cover] = EDGAR.xbrl_report_facts(summary, presentation, xbrl)
+
+    symbols =
+      cover.facts
+      |> Enum.filter(&(&1.concept == "TradingSymbol"))
+      |> Enum.map(& &1.value)
+
+    assert symbols == ["BRK.A"]
+  end
+
+  test "xbrl_fiscal_periods/1 classifies periods and fiscal quarters" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+    {:ok, facts} = EDGAR.xbrl_fiscal_periods(xbrl)
+
+    net_income =
+      Enum.filter(facts, &(&1.fact.concept == "NetIncomeLoss"))
+      |> Enum.map(&{&1.period_type, &1.fiscal_year, &1.fiscal_period})
+
+    assert net_income == [
+             {"quarter", 2024, "Q3"},
+             {"quarter", 2023, "Q3"},
+             {"nine_months", 2024, "9M"},
+             {"nine_months", 2023, "9M"},
+             {"annual", 2023, "FY"}
+           ]
+
+    assets = Enum.filter(facts, &(&1.fact.concept == "Assets"))
+    assert Enum.map(assets, & &1.fiscal_period) == ["Q3", "FY"]
+  end
+
+  test "xbrl_fiscal_periods/2 returns an error on an invalid fiscal year end" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+
+    {:error, error} = EDGAR.xbrl_fiscal_periods(xbrl, "1é2")
+    assert error == "invalid fiscal year end: 1é2"
+
+    {:error, error} = EDGAR.xbrl_fiscal_periods(xbrl, "+9-28")
+    assert error == "invalid fiscal year end: +928"
+  end
+
+  test "xbrl_derived_values/1 derives Q4 and trailing twelve months" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+    {:ok, facts} = EDGAR.xbrl_fiscal_periods(xbrl)
+
+    derived =
+      EDGAR.xbrl_derived_values(facts)
+      |> Enum.filter(&(&1.concept == "NetIncomeLoss"))
+
+    [q4, ttm] = derived
+    assert q4.fiscal_year == 2023
+    assert q4.fiscal_period == "Q4"
+    assert q4.start_date == "2023-07-02"
+    assert q4.end_date == "2023-09-30"
+    assert q4.value == 22_956_000_000.0
+    assert q4.method == "FY - 9M"
+
+    assert ttm.fiscal_year == 2024
+    assert ttm.fiscal_period == "TTM"
+    assert ttm.value == 101_956_000_000.0
+  end
+
+  test "xbrl_cover_page/1 extracts the registrant and document" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+    cover = EDGAR.xbrl_cover_page(xbrl)
+
+    assert cover.document_type == "10-Q"
+    assert cover.document_period_end_date == "2024-06-29"
+    assert cover.is_quarterly_report
+    refute cover.is_amendment
+    assert cover.fiscal_year_focus == 2024
+    assert cover.fiscal_period_focus == "Q3"
+    assert cover.registrant_name == "Apple Inc."
+    assert cover.central_index_key == "0000320193"
+    assert cover.address.city == "Cupertino"
+    assert cover.phone_number == "(408) 996-1010"
+    assert cover.current_reporting_status
+
+    [common | notes] = cover.securities
+    assert common.class_member == "CommonStockMember"
+    assert common.trading_symbol == "AAPL"
+    assert common.exchange == "NASDAQ"
+    assert length(notes) == 2
+
+    [shares] = cover.shares_outstanding
+    assert shares.class_member == nil
+    assert shares.date == "2024-07-12"
+    assert shares.shares == 15_222_259_000
+  end
+
+  test "xbrl_cover_page/1 returns each class of stock" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl.xml")
+    cover = EDGAR.xbrl_cover_page(xbrl)
+
+    assert cover.document_type == "8-K"
+    assert cover.central_index_key == "0001067983"
+    assert cover.phone_number == "(402) 346-1400"
+
+    [class_a, class_b | _notes] = cover.securities
+    assert class_a.class_member == "ClassACommonStockMember"
+    assert class_a.trading_symbol == "BRK.A"
+    assert class_b.trading_symbol == "BRK.B"
+  end
+
+  test "xbrl_standardized_statements/2 resolves line items by concept and formula" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+    {:ok, values} = EDGAR.xbrl_standardized_statements(xbrl)
+
+    quarter =
+      values
+      |> Enum.filter(
+        &(&1.period.start_date == "2024-03-31" and &1.period.end_date == "2024-06-29")
+      )
+      |> Map.new(&{&1.line_item, &1})
+
+    assert quarter["Revenue"].value == 85_777_000_000
+    assert quarter["Revenue"].concept == "RevenueFromContractWithCustomerExcludingAssessedTax"
+    assert quarter["Revenue"].formula == nil
+
+    assert quarter["GrossProfit"].value == 39_678_000_000
+    assert quarter["GrossProfit"].concept == nil
+    assert quarter["GrossProfit"].formula == "Revenue - CostOfRevenue"
+
+    assert quarter["GrossProfit"].inputs == [
+             "RevenueFromContractWithCustomerExcludingAssessedTax",
+             "CostOfGoodsAndServicesSold"
+           ]
+
+    assert quarter["OperatingExpenses"].value == 14_326_000_000
+    assert quarter["OperatingExpenses"].formula == "GrossProfit - OperatingIncome"
+    assert quarter["OperatingIncome"].concept == "OperatingIncomeLoss"
+
+    [assets | _] = Enum.filter(values, &(&1.line_item == "TotalAssets"))
+    assert assets.period.instant == "2024-06-29"
+    assert assets.concept == "Assets"
+    assert assets.value == 331_612_000_000
+  end
+
+  test "xbrl_standardized_statements/2 accepts custom line items" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+
+    line_items = [
+      %{name: "Sales", concepts: ["us-gaap:SalesRevenueNet", "us-gaap:Revenues"], formulas: []},
+      %{name: "WorkingCapital", concepts: [], formulas: ["AssetsCurrent - LiabilitiesCurrent"]}
+    ]
+
+    {:ok, values} = EDGAR.xbrl_standardized_statements(xbrl, line_items)
+    assert Enum.all?(values, &(&1.line_item == "WorkingCapital"))
+    assert hd(values).inputs == ["AssetsCurrent", "LiabilitiesCurrent"]
+
+    {:error, error} =
+      EDGAR.xbrl_standardized_statements(xbrl, [%{name: "Bad", concepts: [], formulas: ["A -"]}])
+
+    assert error == "Bad: invalid formula: A -"
+  end
+
+  test "xbrl_standardized_statements/2 skips formulas that mix units" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+
+    line_items = [
+      %{
+        name: "Mixed",
+        concepts: [],
+        formulas: ["NetIncomeLoss + EarningsPerShareDiluted", "NetIncomeLoss"]
+      }
+    ]
+
+    {:ok, values} = EDGAR.xbrl_standardized_statements(xbrl, line_items)
+    assert Enum.all?(values, &(&1.formula == "NetIncomeLoss"))
+    assert Enum.all?(values, &(&1.unit == "iso4217:USD"))
+  end
+
+  test "xbrl_ratios/2 computes ratios with their input facts" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+    {:ok, ratios} = EDGAR.xbrl_ratios(xbrl)
+
+    quarter =
+      ratios
+      |> Enum.filter(
+        &(&1.period.start_date == "2024-03-31" and &1.period.end_date == "2024-06-29")
+      )
+      |> Map.new(&{&1.name, &1})
+
+    assert_in_delta quarter["NetMargin"].value, 21_448 / 85_777, 1.0e-9
+    assert quarter["NetMargin"].unit == "pure"
+
+    assert Enum.map(quarter["NetMargin"].inputs, & &1.concept) == [
+             "NetIncomeLoss",
+             "RevenueFromContractWithCustomerExcludingAssessedTax"
+           ]
+
+    assert_in_delta quarter["RevenueGrowth"].value, 85_777 / 81_797 - 1, 1.0e-9
+    [current, prior] = quarter["RevenueGrowth"].inputs
+    assert current.period.end_date == "2024-06-29"
+    assert prior.period.end_date == "2023-07-01"
+    assert prior.value == 81_797_000_000
+
+    [equity | _] = quarter["ReturnOnEquity"].inputs |> Enum.reverse()
+    assert equity.concept == "StockholdersEquity"
+    assert equity.period.instant == "2024-06-29"
+
+    [current_ratio | _] = Enum.filter(ratios, &(&1.name == "CurrentRatio"))
+    assert current_ratio.period.instant == "2024-06-29"
+    assert_in_delta current_ratio.value, 125_435 / 131_624, 1.0e-9
+  end
+
+  test "xbrl_ratios/2 evaluates custom expressions" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+
+    {:ok, [working_capital | _]} =
+      EDGAR.xbrl_ratios(xbrl, [
+        %{
+          name: "WorkingCapital",
+          expression: "(us-gaap:AssetsCurrent - LiabilitiesCurrent) / 1000000"
+        }
+      ])
+
+    assert working_capital.value == -6189.0
+    assert working_capital.unit == "iso4217:USD"
+
+    {:error, error} = EDGAR.xbrl_ratios(xbrl, [%{name: "Bad", expression: "sum(Assets)"}])
+    assert error == "Bad: invalid expression: sum(Assets): unknown function sum/1"
+  end
+
+  test "xbrl_ratios/2 resolves standardized line items in expressions" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+
+    {:ok, [free_cash_flow | _]} =
+      EDGAR.xbrl_ratios(xbrl, [%{name: "FreeCashFlow", expression: "FreeCashFlow / 1000000"}])
+
+    assert free_cash_flow.period.end_date == "2024-06-29"
+    assert free_cash_flow.value == 84_904.0
+
+    assert Enum.map(free_cash_flow.inputs, & &1.concept) == [
+             "NetCashProvidedByUsedInOperatingActivities",
+             "PaymentsToAcquirePropertyPlantAndEquipment"
+           ]
+
+    {:ok, ratios} = EDGAR.xbrl_ratios(xbrl, [%{name: "Revenue", expression: "us-gaap:Revenue"}])
+    assert ratios == []
+  end
+
+  test "xbrl_ratios/3 resolves custom line items in expressions" do
+    {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl_10q.xml")
+
+    line_items = [
+      %{name: "WorkingCapital", concepts: [], formulas: ["AssetsCurrent - LiabilitiesCurrent"]}
+    ]
+
+    ratios = [%{name: "WorkingCapital", expression: "WorkingCapital / 1000000"}]
+    {:ok, [working_capital | _]} = EDGAR.xbrl_ratios(xbrl, ratios, line_items)
+
+    assert working_capital.period.instant == "2024-06-29"
+    assert working_capital.value == -6189.0
+    assert Enum.map(working_capital.inputs, & &1.concept) == [
+             "AssetsCurrent",
+             "LiabilitiesCurrent"
+           ]
+
+    bad_line_items = [%{name: "Bad", concepts: [], formulas: ["A -"]}]
+    {:error, error} = EDGAR.xbrl_ratios(xbrl, [%{name: "R", expression: "Bad"}], bad_line_items)
+    assert error == "R: invalid expression: Bad: invalid formula: A -: unexpected end"
+  end
+
   test "xbrl_from_file/1 returns a parsed xbrl filing" do
     {:ok, xbrl} = EDGAR.xbrl_from_file("test/test_data/xbrl.xml")
 